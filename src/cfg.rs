@@ -32,32 +32,772 @@ async fn test_server_basic_usage() {
         let _ = ctx.send_response_body(request_body).await;
     }
 
-    fn error_handle(error: String) {
-        eprintln!("{}", error);
+    async fn error_handle(event: ErrorEvent) {
+        eprintln!("{}", event);
         let _ = std::io::Write::flush(&mut std::io::stderr());
     }
 
-    async fn main() {
+    let server: Server = Server::new();
+    server.enable_nodelay().await;
+    server.disable_linger().await;
+    server.http_line_buffer_size(4096).await;
+    server.websocket_buffer_size(4096).await;
+    server.error_handle(error_handle).await;
+    server.request_middleware(request_middleware).await;
+    server.response_middleware(response_middleware).await;
+    server.route("/", root_route).await;
+    server.route("/websocket", websocket_route).await;
+    server
+        .route("/test/:text", move |ctx: Context| async move {
+            let param: RouteParams = ctx.get_route_params().await;
+            panic!("Test panic {:?}", param);
+        })
+        .await;
+    let client: TestClient = server.test_client();
+    let response: TestResponse = client.get("/").send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(response.get_body_string(), "Hello hyperlane => /");
+    assert_eq!(response.get_header(SERVER), Some(HYPERLANE.to_string()));
+    let websocket: TestWebSocket = client.websocket("/websocket").await;
+    websocket.send("hello").await;
+    assert_eq!(websocket.receive().await, Some(b"hello".to_vec()));
+    let response: TestResponse = client.get("/test/panic").send().await;
+    assert_eq!(*response.get_status_code(), 500);
+    let mut request: Request = Request::default();
+    request.set_path("/");
+    let response: Response = server.oneshot(request).await;
+    assert_eq!(response.get_body(), b"Hello hyperlane => /");
+}
+
+#[tokio::test]
+async fn test_server_graceful_shutdown() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .get("/slow", |_: Context| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            "slow done"
+        })
+        .await;
+    server
+        .get("/fast", |_: Context| async move { "fast" })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let mut idle_stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    idle_stream
+        .write_all(b"GET /fast HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    let mut buffer: [u8; 1024] = [0; 1024];
+    let size: usize = idle_stream.read(&mut buffer).await.unwrap();
+    assert!(String::from_utf8_lossy(&buffer[..size]).contains("fast"));
+    let mut slow_stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    slow_stream
+        .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    let shutdown_result: Result<(), tokio::time::error::Elapsed> =
+        tokio::time::timeout(std::time::Duration::from_secs(5), handle.shutdown()).await;
+    assert!(shutdown_result.is_ok());
+    assert!(!handle.is_running());
+    let mut response: Vec<u8> = vec![];
+    let _ = slow_stream.read_to_end(&mut response).await;
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    assert!(response.contains("200"));
+    assert!(response.contains("slow done"));
+    assert!(response.contains(&format!("{}: {}", CONNECTION, CONNECTION_CLOSE)));
+    let mut remaining: Vec<u8> = vec![];
+    let _ = idle_stream.read_to_end(&mut remaining).await;
+    assert!(remaining.is_empty());
+}
+
+#[tokio::test]
+async fn test_server_shutdown_finishes_partial_request() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .post("/upload", |ctx: Context| async move {
+            let _ = ctx.send_response(200, ctx.get_request_body().await).await;
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let mut stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    stream
+        .write_all(b"POST /upload HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nhe")
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    let shutdown_handle: ServerHandle = handle.clone();
+    let shutdown = tokio::spawn(async move { shutdown_handle.shutdown().await });
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(!handle.is_running());
+    stream.write_all(b"llo").await.unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = stream.read_to_end(&mut response).await;
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    assert!(response.contains("200"));
+    assert!(response.ends_with("hello"));
+    assert!(response.contains(&format!("{}: {}", CONNECTION, CONNECTION_CLOSE)));
+    shutdown.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_server_start_ephemeral_port() {
+    use crate::*;
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    assert_ne!(handle.local_addr().port(), 0);
+    assert!(handle.is_running());
+    handle.shutdown().await;
+    assert!(!handle.is_running());
+    assert!(handle.wait().await.is_ok());
+}
+
+#[cfg(feature = "http2")]
+#[tokio::test]
+async fn test_server_http2_prior_knowledge() {
+    use crate::*;
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .get("/hello", |ctx: Context| async move {
+            ctx.set_response_status_code(200).await;
+            "Hello hyperlane => h2"
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    let (mut client, connection) = h2::client::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request: Http2Request<()> = Http2Request::builder()
+        .method("GET")
+        .uri(format!("http://{}/hello", handle.local_addr()))
+        .body(())
+        .unwrap();
+    let (response, _) = client.send_request(request, true).unwrap();
+    let response: Http2Response<RecvStream> = response.await.unwrap();
+    assert_eq!(response.status(), 200);
+    let mut body: RecvStream = response.into_body();
+    let mut data: Vec<u8> = vec![];
+    while let Some(chunk) = body.data().await {
+        data.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(data, b"Hello hyperlane => h2");
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[cfg(feature = "http2")]
+#[tokio::test]
+async fn test_server_http2_streaming() {
+    use crate::*;
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .get("/stream", |ctx: Context| async move {
+            let name: String = ctx.get_request_query("name").await.unwrap_or_default();
+            let peer: String = ctx.get_socket_addr_or_default_string().await;
+            let _ = ctx.send_response(200, format!("{} {}\n", name, peer)).await;
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            let _ = ctx.send_response_body("done").await;
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    let local_addr: SocketAddr = stream.local_addr().unwrap();
+    let (mut client, connection) = h2::client::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request: Http2Request<()> = Http2Request::builder()
+        .method("GET")
+        .uri(format!(
+            "http://{}/stream?name=hello%20world",
+            handle.local_addr()
+        ))
+        .body(())
+        .unwrap();
+    let (response, _) = client.send_request(request, true).unwrap();
+    let response: Http2Response<RecvStream> = response.await.unwrap();
+    assert_eq!(response.status(), 200);
+    let mut body: RecvStream = response.into_body();
+    let first: Bytes = tokio::time::timeout(std::time::Duration::from_millis(300), body.data())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(first, format!("hello world {}\n", local_addr).as_bytes());
+    let mut rest: Vec<u8> = vec![];
+    while let Some(chunk) = body.data().await {
+        rest.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(rest, b"done");
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_multiple_listeners() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn get_status_line(addr: SocketAddr, path: &str) -> String {
+        let mut stream: TcpStream = TcpStream::connect(addr).await.unwrap();
+        let request: String = format!(
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response: Vec<u8> = vec![];
+        let _ = stream.read_to_end(&mut response).await;
+        let response: String = String::from_utf8_lossy(&response).to_string();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server.listen("admin", "127.0.0.1:0").await;
+    server.get("/", |_: Context| async move { "public" }).await;
+    server
+        .get("/metrics", |_: Context| async move { "metrics" })
+        .await;
+    server.listener_route("admin", "/metrics").await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let public_addr: SocketAddr = handle.local_addr();
+    let admin_addr: SocketAddr = handle.listener_addr("admin").unwrap();
+    assert_ne!(public_addr, admin_addr);
+    assert!(get_status_line(public_addr, "/").await.contains("200"));
+    assert!(get_status_line(admin_addr, "/").await.contains("200"));
+    assert!(
+        get_status_line(public_addr, "/metrics")
+            .await
+            .contains("404")
+    );
+    assert!(
+        get_status_line(admin_addr, "/metrics")
+            .await
+            .contains("200")
+    );
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_pre_bound_listener() {
+    use crate::*;
+
+    let std_listener: std::net::TcpListener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let bound_addr: SocketAddr = std_listener.local_addr().unwrap();
+    let tokio_listener: TcpListener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let admin_addr: SocketAddr = tokio_listener.local_addr().unwrap();
+    let server: Server = Server::new();
+    server.listener(std_listener).await;
+    server.named_listener("admin", tokio_listener).await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    assert_eq!(handle.local_addr(), bound_addr);
+    assert_eq!(handle.listener_addr("admin"), Some(admin_addr));
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_server_timeouts() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .header_read_timeout(std::time::Duration::from_millis(200))
+        .await;
+    server
+        .handler_timeout(std::time::Duration::from_millis(200))
+        .await;
+    server
+        .get("/slow", |_: Context| async move {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            "slow"
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let mut idle_stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = idle_stream.read_to_end(&mut response).await;
+    assert!(String::from_utf8_lossy(&response).contains("408"));
+    let mut slow_stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    slow_stream
+        .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = slow_stream.read_to_end(&mut response).await;
+    assert!(String::from_utf8_lossy(&response).contains("503"));
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_read_phase_timeouts() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn send_in_parts(addr: SocketAddr, parts: &[&str], delay: u64) -> String {
+        let mut stream: TcpStream = TcpStream::connect(addr).await.unwrap();
+        for (index, part) in parts.iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            }
+            let _ = stream.write_all(part.as_bytes()).await;
+        }
+        let mut response: Vec<u8> = vec![];
+        let _ = stream.read_to_end(&mut response).await;
+        let response: String = String::from_utf8_lossy(&response).to_string();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    async fn start_server(header_timeout: u64, body_timeout: u64) -> ServerHandle {
         let server: Server = Server::new();
-        server.host("0.0.0.0").await;
-        server.port(60000).await;
-        server.enable_nodelay().await;
-        server.disable_linger().await;
-        server.http_line_buffer_size(4096).await;
-        server.websocket_buffer_size(4096).await;
-        server.error_handle(error_handle).await;
-        server.request_middleware(request_middleware).await;
-        server.response_middleware(response_middleware).await;
-        server.route("/", root_route).await;
-        server.route("/websocket", websocket_route).await;
+        server.host("127.0.0.1").await;
+        server.port(0).await;
         server
-            .route("/test/:text", move |ctx: Context| async move {
-                let param: RouteParams = ctx.get_route_params().await;
-                panic!("Test panic {:?}", param);
-            })
+            .header_read_timeout(std::time::Duration::from_millis(header_timeout))
             .await;
-        server.run().await.unwrap();
+        server
+            .body_read_timeout(std::time::Duration::from_millis(body_timeout))
+            .await;
+        server.post("/", |_: Context| async move { "ok" }).await;
+        server.start().await.unwrap()
     }
 
-    let _ = tokio::time::timeout(std::time::Duration::from_secs(60), main()).await;
+    let head_start: &str = "POST / HTTP/1.1\r\nHost: localhost\r\n";
+    let head_end: &str = "Content-Length: 4\r\nConnection: close\r\n\r\n";
+    let handle: ServerHandle = start_server(200, 1000).await;
+    let status_line: String =
+        send_in_parts(handle.local_addr(), &[head_start, head_end, "body"], 400).await;
+    assert!(status_line.contains("408"));
+    handle.shutdown().await;
+    let handle: ServerHandle = start_server(1000, 200).await;
+    let head: String = format!("{}{}", head_start, head_end);
+    let status_line: String = send_in_parts(handle.local_addr(), &[&head, "body"], 400).await;
+    assert!(status_line.contains("408"));
+    handle.shutdown().await;
+    let handle: ServerHandle = start_server(300, 300).await;
+    let status_line: String =
+        send_in_parts(handle.local_addr(), &[head_start, head_end, "body"], 200).await;
+    assert!(status_line.contains("200"));
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_server_request_limits() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn get_status_line(addr: SocketAddr, request: &str) -> String {
+        let mut stream: TcpStream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response: Vec<u8> = vec![];
+        let _ = stream.read_to_end(&mut response).await;
+        let response: String = String::from_utf8_lossy(&response).to_string();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server.max_uri_length(16).await;
+    server.max_header_count(4).await;
+    server.max_body_size(8).await;
+    server.post("/", |_: Context| async move { "ok" }).await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let addr: SocketAddr = handle.local_addr();
+    let uri: String = format!("/{}", "a".repeat(32));
+    let request: String = format!("POST {} HTTP/1.1\r\nHost: localhost\r\n\r\n", uri);
+    assert!(get_status_line(addr, &request).await.contains("414"));
+    let request: &str =
+        "POST / HTTP/1.1\r\nHost: localhost\r\nA: 1\r\nB: 2\r\nC: 3\r\nD: 4\r\n\r\n";
+    assert!(get_status_line(addr, request).await.contains("431"));
+    let request: &str =
+        "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 16\r\n\r\n0123456789abcdef";
+    assert!(get_status_line(addr, request).await.contains("413"));
+    let request: &str = "POST / HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n6\r\n012345\r\n6\r\n6789ab\r\n0\r\n\r\n";
+    assert!(get_status_line(addr, request).await.contains("413"));
+    let request: &str = "POST / HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n4\r\n0123\r\n0\r\n\r\n";
+    assert!(get_status_line(addr, request).await.contains("200"));
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_pipelined_requests() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .post("/echo", |ctx: Context| async move {
+            ctx.get_request_body_string().await
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let mut stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    stream
+        .write_all(
+            b"POST /echo HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5\r\nfirst\r\n0\r\n\r\n\
+            POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: 6\r\nConnection: close\r\n\r\n\
+            second",
+        )
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = stream.read_to_end(&mut response).await;
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    let first: usize = response.find("first").unwrap();
+    let second: usize = response.find("second").unwrap();
+    assert!(first < second);
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_route_concurrency() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn get_status_line(addr: SocketAddr) -> String {
+        let mut stream: TcpStream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response: Vec<u8> = vec![];
+        let _ = stream.read_to_end(&mut response).await;
+        let response: String = String::from_utf8_lossy(&response).to_string();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server.max_connections(8).await;
+    server.route_concurrency("/slow", 1).await;
+    server
+        .get("/slow", |_: Context| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            "slow"
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let addr: SocketAddr = handle.local_addr();
+    let (first, second) = tokio::join!(get_status_line(addr), async {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        get_status_line(addr).await
+    });
+    assert!(first.contains("200"));
+    assert!(second.contains("503"));
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_panic_isolation() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server.panic_response(500, "Internal Server Error").await;
+    server
+        .get("/panic", |_: Context| async move {
+            panic!("Test panic");
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let mut stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    stream
+        .write_all(b"GET /panic HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = stream.read_to_end(&mut response).await;
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    assert!(response.contains("500"));
+    assert!(response.contains("Internal Server Error"));
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[tokio::test]
+async fn test_server_error_event() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn send_request(addr: SocketAddr, path: &str) {
+        let mut stream: TcpStream = TcpStream::connect(addr).await.unwrap();
+        let request: String = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response: Vec<u8> = vec![];
+        let _ = stream.read_to_end(&mut response).await;
+    }
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<ErrorEvent>();
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .error_handle(move |event: ErrorEvent| {
+            let sender = sender.clone();
+            async move {
+                let _ = sender.send(event);
+            }
+        })
+        .await;
+    server
+        .middleware(|ctx: Context, next: Next| async move { next.run(ctx).await })
+        .await;
+    server
+        .middleware(|ctx: Context, next: Next| async move {
+            next.run(ctx.clone()).await;
+            if ctx.get_request_path().await == "/after" {
+                panic!("Middleware panic");
+            }
+        })
+        .await;
+    server
+        .route_middleware("/guarded", |_: Context| async move {})
+        .await;
+    server
+        .route_middleware("/guarded", |_: Context| async move {
+            panic!("Route middleware panic");
+        })
+        .await;
+    server
+        .get("/user/:id", |_: Context| async move {
+            panic!("Test panic");
+        })
+        .await;
+    server.get("/guarded", |_: Context| async move {}).await;
+    server.get("/after", |_: Context| async move {}).await;
+    let router: Router = Router::new();
+    router.request_middleware(|_: Context| async move {}).await;
+    router
+        .middleware(|ctx: Context, next: Next| async move {
+            next.run(ctx.clone()).await;
+            if ctx.get_request_path().await == "/nested/after" {
+                panic!("Router middleware panic");
+            }
+        })
+        .await;
+    router
+        .get("/item/:id", |_: Context| async move {
+            panic!("Nested panic");
+        })
+        .await;
+    router.get("/after", |_: Context| async move {}).await;
+    server.nest("/nested", router).await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let addr: SocketAddr = handle.local_addr();
+    let expected_sources: [(&str, ErrorSource); 5] = [
+        ("/user/1", ErrorSource::Handler("/user/:id".to_string())),
+        ("/guarded", ErrorSource::RouteMiddleware(1)),
+        ("/after", ErrorSource::Middleware(1)),
+        (
+            "/nested/item/1",
+            ErrorSource::Handler("/nested/item/:id".to_string()),
+        ),
+        (
+            "/nested/after",
+            ErrorSource::RouterMiddleware("/nested".to_string(), 0),
+        ),
+    ];
+    for (path, expected_source) in expected_sources {
+        send_request(addr, path).await;
+        let event: ErrorEvent = receiver.recv().await.unwrap();
+        assert!(format!("{:?}", event).starts_with("Panic(ErrorRequest"));
+        let ErrorEvent::Panic(error_request, request_panic) = event else {
+            panic!("unexpected error event");
+        };
+        assert_eq!(error_request.get_path(), path);
+        assert_eq!(*error_request.get_source(), expected_source);
+        assert!(request_panic.get_message().contains("panic"));
+    }
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_server_handle_wait_keeps_error() {
+    use crate::*;
+    use std::os::fd::FromRawFd;
+
+    let fd: i32 = unsafe { libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0) };
+    assert!(fd >= 0);
+    let std_listener: std::net::TcpListener = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+    let server: Server = Server::new();
+    server.listener(std_listener).await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    assert!(matches!(
+        handle.wait().await,
+        Err(ServerError::AcceptError(_))
+    ));
+    assert!(matches!(
+        handle.wait().await,
+        Err(ServerError::AcceptError(_))
+    ));
+    assert!(matches!(
+        handle.clone().wait().await,
+        Err(ServerError::AcceptError(_))
+    ));
+    assert!(!handle.is_running());
+}
+
+#[tokio::test]
+async fn test_server_malformed_request() {
+    use crate::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<ErrorEvent>();
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .error_handle(move |event: ErrorEvent| {
+            let sender = sender.clone();
+            async move {
+                let _ = sender.send(event);
+            }
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    let mut stream: TcpStream = TcpStream::connect(handle.local_addr()).await.unwrap();
+    stream.write_all(b"NOT A REQUEST\r\n\r\n").await.unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = stream.read_to_end(&mut response).await;
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    assert!(response.contains("400"));
+    let event: ErrorEvent = receiver.recv().await.unwrap();
+    assert!(matches!(event, ErrorEvent::Read(..)));
+    handle.shutdown().await;
+    assert!(handle.wait().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_server_dynamic_route_literal_path() {
+    use crate::*;
+
+    let server: Server = Server::new();
+    server
+        .get(r"/user/:id(\d+)", |ctx: Context| async move {
+            let id: String = ctx.get_route_param("id").await.unwrap_or_default();
+            let _ = ctx.send_response(200, id).await;
+        })
+        .await;
+    server
+        .get("/user/me", |ctx: Context| async move {
+            let _ = ctx.send_response(200, "me").await;
+        })
+        .await;
+    assert_eq!(server.get_route().read().await.len(), 1);
+    let client: TestClient = server.test_client();
+    let response: TestResponse = client.get(r"/user/:id(\d+)").send().await;
+    assert_eq!(*response.get_status_code(), 404);
+    let response: TestResponse = client.get("/user/42").send().await;
+    assert_eq!(response.get_body_string(), "42");
+    let response: TestResponse = client.get("/user/me").send().await;
+    assert_eq!(response.get_body_string(), "me");
+}
+
+#[tokio::test]
+async fn test_server_head_for_get_route() {
+    use crate::*;
+
+    let server: Server = Server::new();
+    server
+        .get("/hello", |ctx: Context| async move {
+            let _ = ctx.send_response(200, "hello").await;
+        })
+        .await;
+    let client: TestClient = server.test_client();
+    let response: TestResponse = client.request(HEAD, "/hello").send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(response.get_header(CONTENT_LENGTH), Some("5".to_string()));
+    assert!(response.get_body().is_empty());
+    let response: TestResponse = client.delete("/hello").send().await;
+    assert_eq!(*response.get_status_code(), 405);
+    assert_eq!(response.get_header(ALLOW), Some("GET, HEAD".to_string()));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_server_unix_socket() {
+    use crate::*;
+    use std::os::unix::{fs::PermissionsExt, net::UnixListener as StdUnixListener};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let path: PathBuf = env::temp_dir().join(format!("hyperlane-{}.sock", process::id()));
+    drop(StdUnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+    let server: Server = Server::new();
+    server.unix_socket(&path).await;
+    server.unix_socket_permissions(0o660).await;
+    server
+        .get("/peer", |ctx: Context| async move {
+            let peer: String = ctx.get_socket_addr_or_default_string().await;
+            let _ = ctx.send_response(200, peer).await;
+        })
+        .await;
+    let handle: ServerHandle = server.start().await.unwrap();
+    assert_eq!(handle.unix_socket_path(), Some(path.as_path()));
+    let mode: u32 = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o660);
+    let mut stream: UnixStream = UnixStream::connect(&path).await.unwrap();
+    stream
+        .write_all(b"GET /peer HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = vec![];
+    stream.read_to_end(&mut response).await.unwrap();
+    let response: String = String::from_utf8_lossy(&response).to_string();
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.contains(&format!("unix:{} pid={}", path.display(), process::id())));
+    let busy: Server = Server::new();
+    busy.unix_socket(&path).await;
+    assert!(busy.start().await.is_err());
+    handle.shutdown().await;
+    assert!(!path.exists());
+    std::fs::write(&path, b"not a socket").unwrap();
+    let server: Server = Server::new();
+    server.unix_socket(&path).await;
+    assert!(server.start().await.is_err());
+    assert!(path.exists());
+    std::fs::remove_file(&path).unwrap();
 }
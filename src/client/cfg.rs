@@ -0,0 +1,181 @@
+use crate::*;
+
+#[test]
+fn test_parse_set_cookie() {
+    assert_eq!(
+        parse_set_cookie("session=abc; Path=/; HttpOnly"),
+        Some(("session".to_string(), Some("abc".to_string())))
+    );
+    assert_eq!(
+        parse_set_cookie("session=; Max-Age=0"),
+        Some(("session".to_string(), None))
+    );
+    assert_eq!(parse_set_cookie("invalid"), None);
+    let mut cookies: TestCookieJar = hash_map_xx_hash3_64();
+    cookies.insert("b".to_string(), "2".to_string());
+    cookies.insert("a".to_string(), "1".to_string());
+    assert_eq!(format_cookie_header(&cookies), "a=1; b=2");
+}
+
+#[test]
+fn test_parse_response() {
+    assert_eq!(
+        parse_response_head(b"HTTP/1.1 201 Created\r\nContent-Type: text/plain\r\n\r\n"),
+        Some((
+            201,
+            vec![("Content-Type".to_string(), "text/plain".to_string())]
+        ))
+    );
+    assert_eq!(parse_response_head(b"HTTP/1.1\r\n\r\n"), None);
+    assert_eq!(
+        decode_chunk(b"3\r\nabc\r\n0\r\n\r\n"),
+        Some((b"abc".to_vec(), 8))
+    );
+    assert_eq!(
+        decode_chunk(b"2;ext=1\r\nde\r\n"),
+        Some((b"de".to_vec(), 13))
+    );
+    assert_eq!(
+        decode_chunk(b"0\r\nExpires: never\r\n\r\n"),
+        Some((vec![], 25))
+    );
+    assert_eq!(decode_chunk(b"3\r\nab"), None);
+    assert_eq!(decode_chunk(b"0\r\n"), None);
+}
+
+#[tokio::test]
+async fn test_client_request() {
+    #[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+    struct User {
+        name: String,
+    }
+
+    let server: Server = Server::new();
+    server
+        .post("/login", |ctx: Context| async move {
+            let user: User = ctx.get_request_body_json().await.unwrap();
+            ctx.set_response_header(SET_COOKIE, format!("user={}; Path=/", user.name))
+                .await;
+            let _ = ctx.send_response(200, "").await;
+        })
+        .await;
+    server
+        .get("/me", |ctx: Context| async move {
+            let cookie: String = ctx.get_request_header(COOKIE).await.unwrap_or_default();
+            let name: &str = cookie.trim_start_matches("user=");
+            Json(User {
+                name: name.to_string(),
+            })
+        })
+        .await;
+    server
+        .get("/stream", |ctx: Context| async move {
+            let _ = ctx.send_response(200, "a").await;
+            let _ = ctx.send_response_body("b").await;
+            let _ = ctx.send_response_body("c").await;
+        })
+        .await;
+    let client: TestClient = server.test_client();
+    let user: User = User {
+        name: "hyperlane".to_string(),
+    };
+    let response: TestResponse = client.post("/login").json(&user).send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(
+        client.get_cookie("user").await,
+        Some("hyperlane".to_string())
+    );
+    let response: TestResponse = client.get("/me").send().await;
+    assert_eq!(response.get_body_json::<User>().unwrap(), user);
+    let response: TestResponse = client.get("/stream").send().await;
+    assert_eq!(response.get_body_string(), "abc");
+    let response: TestResponse = client.get("/missing").send().await;
+    assert_eq!(*response.get_status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_client_connection_limits() {
+    let server: Server = Server::new();
+    server.max_body_size(4).await;
+    server.max_uri_length(16).await;
+    server
+        .post("/echo", |ctx: Context| async move {
+            let body: Vec<u8> = ctx.get_request_body().await;
+            let _ = ctx.send_response(200, body).await;
+        })
+        .await;
+    let client: TestClient = server.test_client();
+    let response: TestResponse = client.post("/echo").body("abcd").send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(response.get_body_string(), "abcd");
+    let response: TestResponse = client.post("/echo").body("abcde").send().await;
+    assert_eq!(*response.get_status_code(), 413);
+    let response: TestResponse = client.post("/echo?padding=abcdefgh").send().await;
+    assert_eq!(*response.get_status_code(), 414);
+}
+
+#[tokio::test]
+async fn test_client_websocket() {
+    let server: Server = Server::new();
+    server
+        .get("/websocket", |ctx: Context| async move {
+            let request_body: Vec<u8> = ctx.get_request_body().await;
+            let _ = ctx.send_response_body(request_body).await;
+        })
+        .await;
+    let websocket: TestWebSocket = server.test_client().websocket("/websocket").await;
+    assert_eq!(*websocket.get_handshake().get_status_code(), 101);
+    assert!(
+        websocket
+            .get_handshake()
+            .get_header(SEC_WEB_SOCKET_ACCEPT)
+            .is_some()
+    );
+    websocket.send("ping").await;
+    assert_eq!(websocket.receive().await, Some(b"ping".to_vec()));
+    websocket.send(vec![1; 200]).await;
+    assert_eq!(websocket.receive().await, Some(vec![1; 200]));
+}
+
+#[tokio::test]
+async fn test_client_response_stream() {
+    let server: Server = Server::new();
+    server
+        .get("/events", |ctx: Context| async move {
+            let _ = ctx.send_response(200, "first").await;
+            sleep(Duration::from_millis(50)).await;
+            let _ = ctx.send_response_body("second").await;
+        })
+        .await;
+    let client: TestClient = server.test_client();
+    let mut response: TestResponseStream = client.get("/events").send_stream().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(
+        response.get_header(TRANSFER_ENCODING),
+        Some(CHUNKED.to_string())
+    );
+    assert_eq!(response.next_chunk().await, Some(b"first".to_vec()));
+    assert_eq!(response.next_chunk().await, Some(b"second".to_vec()));
+    assert_eq!(response.next_chunk().await, None);
+}
+
+#[tokio::test]
+async fn test_client_repeated_set_cookie() {
+    let head: TestResponseHead = parse_response_head(
+        b"HTTP/1.1 200 OK\r\nSet-Cookie: a=1; Path=/\r\nSet-Cookie: b=2\r\n\r\n",
+    )
+    .unwrap();
+    let client: TestClient = Server::new().test_client();
+    client.store_cookies(&head.1).await;
+    let response: TestResponse = TestResponse::new(head, vec![]);
+    assert_eq!(
+        response.get_header_values(SET_COOKIE),
+        vec!["a=1; Path=/".to_string(), "b=2".to_string()]
+    );
+    assert_eq!(
+        response.get_header(SET_COOKIE),
+        Some("a=1; Path=/".to_string())
+    );
+    assert_eq!(client.get_cookie("a").await, Some("1".to_string()));
+    assert_eq!(client.get_cookie("b").await, Some("2".to_string()));
+}
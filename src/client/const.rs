@@ -0,0 +1,6 @@
+pub(crate) const TEST_CLIENT_HOST: &str = "localhost";
+pub(crate) const TEST_WEBSOCKET_KEY: &str = "dGhlIHNhbXBsZSBub25jZQ==";
+pub(crate) const COOKIE_MAX_AGE: &str = "max-age";
+pub(crate) const COOKIE_SEPARATOR: &str = "; ";
+pub(crate) const TEST_WEBSOCKET_MASK: [u8; 4] = [0x37, 0xfa, 0x21, 0x3d];
+pub(crate) const TEST_CLIENT_BUFFER_SIZE: usize = 64 * 1024;
@@ -0,0 +1,9 @@
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TestBodyKind {
+    Empty,
+    Length(usize),
+    Chunked,
+    Close,
+}
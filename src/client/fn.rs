@@ -0,0 +1,74 @@
+use crate::*;
+
+pub(crate) fn parse_set_cookie(value: &str) -> OptionSetCookie {
+    let mut parts = value.split(';').map(str::trim);
+    let (name, cookie_value) = parts.next()?.split_once('=')?;
+    let name: &str = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let expired: bool = parts.any(|attribute: &str| {
+        attribute
+            .split_once('=')
+            .is_some_and(|(key, value): (&str, &str)| {
+                key.trim().eq_ignore_ascii_case(COOKIE_MAX_AGE)
+                    && value.trim().parse::<i64>().is_ok_and(|age: i64| age <= 0)
+            })
+    });
+    let cookie_value: Option<String> = (!expired).then(|| cookie_value.trim().to_string());
+    Some((name.to_string(), cookie_value))
+}
+
+pub(crate) fn format_cookie_header(cookies: &TestCookieJar) -> String {
+    let mut pairs: Vec<String> = cookies
+        .iter()
+        .map(|(name, value): (&String, &String)| format!("{}={}", name, value))
+        .collect();
+    pairs.sort();
+    pairs.join(COOKIE_SEPARATOR)
+}
+
+pub(crate) fn parse_response_head(head: &[u8]) -> OptionTestResponseHead {
+    let head: &str = std::str::from_utf8(head).ok()?;
+    let mut lines = head.lines();
+    let status_code: ResponseStatusCode = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let headers: VecTestHeader = lines
+        .filter_map(|line: &str| line.split_once(':'))
+        .map(|(key, value): (&str, &str)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some((status_code, headers))
+}
+
+pub(crate) fn decode_chunk(buffer: &[u8]) -> OptionTestChunk {
+    let line_end: usize = find_subslice(buffer, HTTP_LINE_END)?;
+    let size: usize = parse_chunk_size(&buffer[..line_end]).ok()?;
+    if size == 0 {
+        let trailer_end: usize = find_subslice(&buffer[line_end..], HTTP_HEAD_END)?;
+        return Some((vec![], line_end + trailer_end + HTTP_HEAD_END.len()));
+    }
+    let start: usize = line_end + HTTP_LINE_END.len();
+    let end: usize = start.checked_add(size)?;
+    let chunk: &[u8] = buffer.get(start..end)?;
+    if buffer.get(end..end + HTTP_LINE_END.len())? != HTTP_LINE_END {
+        return None;
+    }
+    Some((chunk.to_vec(), end + HTTP_LINE_END.len()))
+}
+
+pub(crate) fn test_body_kind(method: &str, head: &TestResponseHead) -> TestBodyKind {
+    let (status_code, headers): &TestResponseHead = head;
+    if method.eq_ignore_ascii_case(HEAD) || !status_has_body(*status_code) {
+        return TestBodyKind::Empty;
+    }
+    let is_chunked: bool = headers.iter().any(|(key, value): &TestHeader| {
+        key.eq_ignore_ascii_case(TRANSFER_ENCODING) && value.eq_ignore_ascii_case(CHUNKED)
+    });
+    if is_chunked {
+        return TestBodyKind::Chunked;
+    }
+    headers
+        .iter()
+        .find(|(key, _): &&TestHeader| key.eq_ignore_ascii_case(CONTENT_LENGTH))
+        .and_then(|(_, value): &TestHeader| value.parse().ok())
+        .map_or(TestBodyKind::Close, TestBodyKind::Length)
+}
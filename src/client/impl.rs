@@ -0,0 +1,393 @@
+use crate::*;
+
+impl TestClient {
+    pub fn new(server: &Server) -> Self {
+        Self {
+            server: server.clone(),
+            cookies: arc_rwlock(hash_map_xx_hash3_64()),
+        }
+    }
+
+    pub fn request<M, P>(&self, method: M, path: P) -> TestRequest
+    where
+        M: Into<String>,
+        P: Into<String>,
+    {
+        TestRequest {
+            client: self.clone(),
+            method: method.into(),
+            path: path.into(),
+            headers: vec![],
+            body: vec![],
+        }
+    }
+
+    pub fn get<P>(&self, path: P) -> TestRequest
+    where
+        P: Into<String>,
+    {
+        self.request(GET, path)
+    }
+
+    pub fn post<P>(&self, path: P) -> TestRequest
+    where
+        P: Into<String>,
+    {
+        self.request(POST, path)
+    }
+
+    pub fn put<P>(&self, path: P) -> TestRequest
+    where
+        P: Into<String>,
+    {
+        self.request(PUT, path)
+    }
+
+    pub fn patch<P>(&self, path: P) -> TestRequest
+    where
+        P: Into<String>,
+    {
+        self.request(PATCH, path)
+    }
+
+    pub fn delete<P>(&self, path: P) -> TestRequest
+    where
+        P: Into<String>,
+    {
+        self.request(DELETE, path)
+    }
+
+    pub async fn websocket<P>(&self, path: P) -> TestWebSocket
+    where
+        P: Into<String>,
+    {
+        let request: TestRequest = self
+            .get(path)
+            .header(UPGRADE, WEBSOCKET)
+            .header(SEC_WEBSOCKET_KEY, TEST_WEBSOCKET_KEY);
+        let mut stream: DuplexStream = self.get_server().connect_test_client().await;
+        request.write_to(&mut stream, UPGRADE).await;
+        let (mut reader, writer) = split(stream);
+        let mut read_buffer: Vec<u8> = vec![];
+        let head_end: usize = loop {
+            if let Some(head_end) = find_subslice(&read_buffer, HTTP_HEAD_END) {
+                break head_end + HTTP_HEAD_END.len();
+            }
+            if Self::read_stream(&mut reader, &mut read_buffer).await == 0 {
+                panic!("connection closed before the websocket handshake");
+            }
+        };
+        let head: TestResponseHead = Self::parse_head(&read_buffer[..head_end]);
+        read_buffer.drain(..head_end);
+        self.store_cookies(&head.1).await;
+        TestWebSocket {
+            handshake: TestResponse::new(head, vec![]),
+            reader: arc_rwlock(reader),
+            writer: arc_rwlock(writer),
+            read_buffer: arc_rwlock(read_buffer),
+        }
+    }
+
+    pub async fn get_cookie(&self, name: &str) -> OptionString {
+        self.get_cookies().read().await.get(name).cloned()
+    }
+
+    pub async fn set_cookie<N, V>(&self, name: N, value: V) -> &Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.get_cookies()
+            .write()
+            .await
+            .insert(name.into(), value.into());
+        self
+    }
+
+    pub async fn clear_cookies(&self) -> &Self {
+        self.get_cookies().write().await.clear();
+        self
+    }
+
+    pub(crate) async fn store_cookies(&self, headers: &VecTestHeader) {
+        let mut cookies: RwLockWriteGuard<TestCookieJar> = self.get_cookies().write().await;
+        for (key, value) in headers.iter() {
+            if !key.eq_ignore_ascii_case(SET_COOKIE) {
+                continue;
+            }
+            match parse_set_cookie(value) {
+                Some((name, Some(cookie_value))) => {
+                    cookies.insert(name, cookie_value);
+                }
+                Some((name, None)) => {
+                    cookies.remove(&name);
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn parse_head(head: &[u8]) -> TestResponseHead {
+        match parse_response_head(head) {
+            Some(head) => head,
+            None => panic!("invalid response head: {}", String::from_utf8_lossy(head)),
+        }
+    }
+
+    async fn read_stream<R>(reader: &mut R, read_buffer: &mut Vec<u8>) -> usize
+    where
+        R: AsyncRead + Unpin,
+    {
+        match reader.read_buf(read_buffer).await {
+            Ok(size) => size,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl TestRequest {
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn body<T>(mut self, body: T) -> Self
+    where
+        T: Into<RequestBody>,
+    {
+        self.body = body.into();
+        self
+    }
+
+    pub fn json<T>(self, data: &T) -> Self
+    where
+        T: Serialize,
+    {
+        let body: Vec<u8> = serde_json::to_vec(data).unwrap_or_default();
+        self.header(CONTENT_TYPE, APPLICATION_JSON).body(body)
+    }
+
+    pub async fn send(self) -> TestResponse {
+        self.send_stream().await.into_response().await
+    }
+
+    pub async fn send_stream(self) -> TestResponseStream {
+        let client: &TestClient = self.get_client();
+        let mut stream: DuplexStream = client.get_server().connect_test_client().await;
+        self.write_to(&mut stream, CONNECTION_CLOSE).await;
+        let mut read_buffer: Vec<u8> = vec![];
+        let head_end: usize = loop {
+            if let Some(head_end) = find_subslice(&read_buffer, HTTP_HEAD_END) {
+                break head_end + HTTP_HEAD_END.len();
+            }
+            if TestClient::read_stream(&mut stream, &mut read_buffer).await == 0 {
+                panic!(
+                    "incomplete response: {}",
+                    String::from_utf8_lossy(&read_buffer)
+                );
+            }
+        };
+        let head: TestResponseHead = TestClient::parse_head(&read_buffer[..head_end]);
+        read_buffer.drain(..head_end);
+        client.store_cookies(&head.1).await;
+        let body_kind: TestBodyKind = test_body_kind(self.get_method(), &head);
+        let (status_code, headers): TestResponseHead = head;
+        TestResponseStream {
+            status_code,
+            headers,
+            stream,
+            read_buffer,
+            body_kind,
+        }
+    }
+
+    pub(crate) async fn write_to(&self, stream: &mut DuplexStream, connection: &str) {
+        let request_bytes: Vec<u8> = self.to_bytes(connection).await;
+        if let Err(err) = stream.write_all(&request_bytes).await {
+            panic!("{}", err);
+        }
+    }
+
+    pub(crate) async fn to_bytes(&self, connection: &str) -> Vec<u8> {
+        let has_header = |name: &str| {
+            self.get_headers()
+                .iter()
+                .any(|(key, _): &TestHeader| key.eq_ignore_ascii_case(name))
+        };
+        let mut headers: VecTestHeader = vec![(CONNECTION.to_string(), connection.to_string())];
+        if !has_header(HOST) {
+            headers.push((HOST.to_string(), TEST_CLIENT_HOST.to_string()));
+        }
+        let cookies: TestCookieJar = self.get_client().get_cookies().read().await.clone();
+        if !has_header(COOKIE) && !cookies.is_empty() {
+            headers.push((COOKIE.to_string(), format_cookie_header(&cookies)));
+        }
+        headers.push((
+            CONTENT_LENGTH.to_string(),
+            self.get_body().len().to_string(),
+        ));
+        headers.extend(
+            self.get_headers()
+                .iter()
+                .filter(|(key, _): &&TestHeader| {
+                    !key.eq_ignore_ascii_case(CONNECTION)
+                        && !key.eq_ignore_ascii_case(CONTENT_LENGTH)
+                })
+                .cloned(),
+        );
+        let mut request_bytes: Vec<u8> = format!(
+            "{} {} {}\r\n",
+            self.get_method(),
+            self.get_path(),
+            HTTP_VERSION_1_1
+        )
+        .into_bytes();
+        for (key, value) in headers.iter() {
+            request_bytes.extend_from_slice(format!("{}: {}\r\n", key, value).as_bytes());
+        }
+        request_bytes.extend_from_slice(HTTP_LINE_END);
+        request_bytes.extend_from_slice(self.get_body());
+        request_bytes
+    }
+}
+
+impl TestResponse {
+    pub(crate) fn new(head: TestResponseHead, body: ResponseBody) -> Self {
+        let (status_code, headers): TestResponseHead = head;
+        Self {
+            status_code,
+            headers,
+            body,
+        }
+    }
+
+    pub fn get_header(&self, key: &str) -> OptionString {
+        self.get_header_values(key).into_iter().next()
+    }
+
+    pub fn get_header_values(&self, key: &str) -> Vec<String> {
+        self.get_headers()
+            .iter()
+            .filter(|(header_key, _): &&TestHeader| header_key.eq_ignore_ascii_case(key))
+            .map(|(_, value): &TestHeader| value.clone())
+            .collect()
+    }
+
+    pub fn get_body_string(&self) -> String {
+        String::from_utf8_lossy(self.get_body()).to_string()
+    }
+
+    pub fn get_body_json<T>(&self) -> ResultSerdeJsonError<T>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_slice(self.get_body())
+    }
+}
+
+impl TestResponseStream {
+    pub fn get_header(&self, key: &str) -> OptionString {
+        self.get_headers()
+            .iter()
+            .find(|(header_key, _): &&TestHeader| header_key.eq_ignore_ascii_case(key))
+            .map(|(_, value): &TestHeader| value.clone())
+    }
+
+    pub async fn next_chunk(&mut self) -> OptionResponseBody {
+        loop {
+            match self.body_kind {
+                TestBodyKind::Empty | TestBodyKind::Length(0) => return None,
+                TestBodyKind::Length(remaining) if !self.read_buffer.is_empty() => {
+                    let size: usize = remaining.min(self.read_buffer.len());
+                    self.body_kind = TestBodyKind::Length(remaining - size);
+                    return Some(self.read_buffer.drain(..size).collect());
+                }
+                TestBodyKind::Close if !self.read_buffer.is_empty() => {
+                    return Some(take(&mut self.read_buffer));
+                }
+                TestBodyKind::Chunked => {
+                    if let Some((chunk, size)) = decode_chunk(&self.read_buffer) {
+                        self.read_buffer.drain(..size);
+                        if chunk.is_empty() {
+                            self.body_kind = TestBodyKind::Empty;
+                            return None;
+                        }
+                        return Some(chunk);
+                    }
+                }
+                _ => {}
+            }
+            if TestClient::read_stream(&mut self.stream, &mut self.read_buffer).await == 0 {
+                match self.body_kind {
+                    TestBodyKind::Close => {
+                        self.body_kind = TestBodyKind::Empty;
+                        return None;
+                    }
+                    _ => panic!(
+                        "incomplete response body: {}",
+                        String::from_utf8_lossy(&self.read_buffer)
+                    ),
+                }
+            }
+        }
+    }
+
+    pub async fn into_response(mut self) -> TestResponse {
+        let mut body: ResponseBody = vec![];
+        while let Some(chunk) = self.next_chunk().await {
+            body.extend(chunk);
+        }
+        TestResponse::new((self.status_code, self.headers), body)
+    }
+}
+
+impl TestWebSocket {
+    pub async fn send<T>(&self, body: T) -> &Self
+    where
+        T: Into<RequestBody>,
+    {
+        let body: RequestBody = body.into();
+        self.write_frame(WEBSOCKET_OPCODE_BINARY, &body).await;
+        self
+    }
+
+    pub async fn receive(&self) -> OptionResponseBody {
+        let mut read_buffer: RwLockWriteGuard<Vec<u8>> = self.get_read_buffer().write().await;
+        let mut message: ResponseBody = vec![];
+        loop {
+            let Some(((is_final, opcode, payload), size)) = decode_websocket_frame(&read_buffer)
+            else {
+                let mut reader: RwLockWriteGuard<ReadHalf<DuplexStream>> =
+                    self.get_reader().write().await;
+                if TestClient::read_stream(&mut reader, &mut read_buffer).await == 0 {
+                    return None;
+                }
+                continue;
+            };
+            read_buffer.drain(..size);
+            match opcode {
+                WEBSOCKET_OPCODE_CLOSE => return None,
+                WEBSOCKET_OPCODE_PING => {
+                    self.write_frame(WEBSOCKET_OPCODE_PONG, &payload).await;
+                    continue;
+                }
+                WEBSOCKET_OPCODE_PONG => continue,
+                _ => message.extend(payload),
+            }
+            if is_final {
+                return Some(message);
+            }
+        }
+    }
+
+    async fn write_frame(&self, opcode: u8, payload: &[u8]) {
+        let frame: Vec<u8> = encode_websocket_frame(opcode, payload, Some(TEST_WEBSOCKET_MASK));
+        if let Err(err) = self.get_writer().write().await.write_all(&frame).await {
+            panic!("{}", err);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+#[derive(Clone, Getter)]
+pub struct TestClient {
+    #[get(pub(crate))]
+    pub(super) server: Server,
+    #[get(pub(crate))]
+    pub(super) cookies: ArcRwLockTestCookieJar,
+}
+
+#[derive(Clone, Getter)]
+pub struct TestRequest {
+    #[get(pub(crate))]
+    pub(super) client: TestClient,
+    #[get(pub)]
+    pub(super) method: String,
+    #[get(pub)]
+    pub(super) path: String,
+    #[get(pub(crate))]
+    pub(super) headers: VecTestHeader,
+    #[get(pub)]
+    pub(super) body: RequestBody,
+}
+
+#[derive(Clone, Debug, Getter)]
+pub struct TestResponse {
+    #[get(pub)]
+    pub(super) status_code: ResponseStatusCode,
+    #[get(pub)]
+    pub(super) headers: VecTestHeader,
+    #[get(pub)]
+    pub(super) body: ResponseBody,
+}
+
+#[derive(Getter)]
+pub struct TestResponseStream {
+    #[get(pub)]
+    pub(super) status_code: ResponseStatusCode,
+    #[get(pub)]
+    pub(super) headers: VecTestHeader,
+    pub(super) stream: DuplexStream,
+    pub(super) read_buffer: Vec<u8>,
+    pub(super) body_kind: TestBodyKind,
+}
+
+#[derive(Clone, Getter)]
+pub struct TestWebSocket {
+    #[get(pub)]
+    pub(super) handshake: TestResponse,
+    #[get(pub(crate))]
+    pub(super) reader: ArcRwLockReadHalfDuplexStream,
+    #[get(pub(crate))]
+    pub(super) writer: ArcRwLockWriteHalfDuplexStream,
+    #[get(pub(crate))]
+    pub(super) read_buffer: ArcRwLockVecU8,
+}
@@ -0,0 +1,15 @@
+use crate::*;
+
+pub(crate) type TestHeader = (String, String);
+pub(crate) type VecTestHeader = Vec<TestHeader>;
+pub(crate) type TestCookieJar = HashMapXxHash3_64<String, String>;
+pub(crate) type ArcRwLockTestCookieJar = ArcRwLock<TestCookieJar>;
+pub(crate) type OptionSetCookie = Option<(String, Option<String>)>;
+pub(crate) type TestResponseHead = (ResponseStatusCode, VecTestHeader);
+pub(crate) type OptionTestResponseHead = Option<TestResponseHead>;
+pub(crate) type OptionResponseBody = Option<ResponseBody>;
+pub(crate) type TestChunk = (ResponseBody, usize);
+pub(crate) type OptionTestChunk = Option<TestChunk>;
+pub(crate) type ArcRwLockReadHalfDuplexStream = ArcRwLock<ReadHalf<DuplexStream>>;
+pub(crate) type ArcRwLockWriteHalfDuplexStream = ArcRwLock<WriteHalf<DuplexStream>>;
+pub(crate) type ArcRwLockVecU8 = ArcRwLock<Vec<u8>>;
@@ -10,15 +10,86 @@ impl<'a> Default for ServerConfig<'a> {
             nodelay: DEFAULT_NODELAY,
             linger: DEFAULT_LINGER,
             ttl: DEFAULT_TTI,
+            header_read_timeout: None,
+            body_read_timeout: None,
+            keep_alive_timeout: None,
+            handler_timeout: None,
+            write_timeout: None,
+            max_body_size: None,
+            max_header_bytes: None,
+            max_header_count: None,
+            max_uri_length: None,
+            unix_socket: None,
+            unix_socket_mode: None,
+            unix_socket_owner: (None, None),
+            connection_semaphore: None,
+            in_flight_semaphore: None,
+            listeners: vec![],
             disable_inner_http_handle: arc_rwlock(hash_set_xx_hash3_64()),
             disable_inner_websocket_handle: arc_rwlock(hash_set_xx_hash3_64()),
             route_matcher: arc_rwlock(RouteMatcher::new()),
+            #[cfg(feature = "tls")]
+            tls: Arc::default(),
             error_handle: Arc::new(print_error_handle),
+            panic_status_code: 500,
+            panic_response_body: ResponseBody::default(),
         }
     }
 }
 
 impl<'a> ServerConfig<'a> {
+    pub fn add_listener(&mut self, name: String, addr: String) -> &mut Self {
+        self.listeners.push((name, addr));
+        self
+    }
+
+    pub fn set_max_connections(&mut self, max_connections: usize) -> &mut Self {
+        self.connection_semaphore = Some(Arc::new(Semaphore::new(max_connections)));
+        self
+    }
+
+    pub fn set_max_in_flight_requests(&mut self, max_in_flight_requests: usize) -> &mut Self {
+        self.in_flight_semaphore = Some(Arc::new(Semaphore::new(max_in_flight_requests)));
+        self
+    }
+
+    pub fn try_acquire_in_flight(&self) -> ResultOptionOwnedSemaphorePermit {
+        match &self.in_flight_semaphore {
+            Some(semaphore) => semaphore.clone().try_acquire_owned().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn check_request_limits(
+        &self,
+        uri_length: usize,
+        header_bytes: usize,
+        header_count: usize,
+        content_length: OptionUsize,
+    ) -> Result<(), ResponseStatusCode> {
+        if self
+            .max_uri_length
+            .is_some_and(|max: usize| uri_length > max)
+        {
+            return Err(414);
+        }
+        if self
+            .max_header_bytes
+            .is_some_and(|max: usize| header_bytes > max)
+            || self
+                .max_header_count
+                .is_some_and(|max: usize| header_count > max)
+        {
+            return Err(431);
+        }
+        if let (Some(max), Some(content_length)) = (self.max_body_size, content_length) {
+            if content_length > max {
+                return Err(413);
+            }
+        }
+        Ok(())
+    }
+
     pub async fn contains_disable_inner_http_handle(&self, route: &'a str) -> bool {
         if self
             .get_disable_inner_http_handle()
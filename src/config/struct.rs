@@ -9,11 +9,34 @@ pub(crate) struct ServerConfig<'a> {
     pub(super) nodelay: bool,
     pub(super) linger: OptionDuration,
     pub(super) ttl: OptionU32,
+    pub(super) header_read_timeout: OptionDuration,
+    pub(super) body_read_timeout: OptionDuration,
+    pub(super) keep_alive_timeout: OptionDuration,
+    pub(super) handler_timeout: OptionDuration,
+    pub(super) write_timeout: OptionDuration,
+    pub(super) max_body_size: OptionUsize,
+    pub(super) max_header_bytes: OptionUsize,
+    pub(super) max_header_count: OptionUsize,
+    pub(super) max_uri_length: OptionUsize,
+    pub(super) unix_socket: OptionPathBuf,
+    pub(super) unix_socket_mode: OptionU32,
+    pub(super) unix_socket_owner: UnixSocketOwner,
+    #[set(skip)]
+    pub(super) connection_semaphore: OptionArcSemaphore,
+    #[set(skip)]
+    pub(super) in_flight_semaphore: OptionArcSemaphore,
+    #[set(skip)]
+    pub(super) listeners: VecListenerAddr,
     #[set(skip)]
     pub(super) disable_inner_http_handle: ArcRwLock<HashSetXxHash3_64<String>>,
     #[set(skip)]
     pub(super) disable_inner_websocket_handle: ArcRwLock<HashSetXxHash3_64<String>>,
     #[set(skip)]
     pub(super) route_matcher: ArcRwLockRouteMatcher,
+    #[cfg(feature = "tls")]
+    #[set(skip)]
+    pub(super) tls: ArcTlsSettings,
     pub(super) error_handle: ArcErrorHandle,
+    pub(super) panic_status_code: ResponseStatusCode,
+    pub(super) panic_response_body: ResponseBody,
 }
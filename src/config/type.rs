@@ -1,3 +1,11 @@
 use crate::*;
 
 pub(crate) type ArcRwLockServerConfig<'a> = ArcRwLock<ServerConfig<'a>>;
+pub(crate) type ListenerAddr = (String, String);
+pub(crate) type VecListenerAddr = Vec<ListenerAddr>;
+pub(crate) type ArcSemaphore = Arc<Semaphore>;
+pub(crate) type OptionArcSemaphore = Option<ArcSemaphore>;
+pub(crate) type OptionOwnedSemaphorePermit = Option<OwnedSemaphorePermit>;
+pub(crate) type ResultOptionOwnedSemaphorePermit =
+    Result<OptionOwnedSemaphorePermit, TryAcquireError>;
+pub(crate) type UnixSocketOwner = (OptionU32, OptionU32);
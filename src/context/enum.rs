@@ -0,0 +1,8 @@
+use crate::*;
+
+#[derive(Debug, Clone)]
+pub(crate) enum ResponseFrame {
+    Head(ResponseStatusCode, ResponseHeaders),
+    Body(ResponseBody),
+    End,
+}
@@ -14,6 +14,12 @@ impl Context {
         ctx
     }
 
+    pub(crate) fn detached() -> Self {
+        let mut inner_ctx: InnerContext = InnerContext::default();
+        inner_ctx.set_detached(true);
+        Context::from_inner_context(inner_ctx)
+    }
+
     async fn get_read_lock(&self) -> RwLockReadInnerContext {
         self.0.read().await
     }
@@ -45,7 +51,7 @@ impl Context {
     pub async fn get_socket_addr(&self) -> OptionSocketAddr {
         let stream_result: OptionArcRwLockStream = self.get_stream().await;
         if stream_result.is_none() {
-            return None;
+            return *self.get_read_lock().await.get_peer_addr();
         }
         let socket_addr_opt: OptionSocketAddr = stream_result
             .unwrap()
@@ -59,7 +65,11 @@ impl Context {
     pub async fn get_socket_addr_or_default(&self) -> SocketAddr {
         let stream_result: OptionArcRwLockStream = self.get_stream().await;
         if stream_result.is_none() {
-            return DEFAULT_SOCKET_ADDR;
+            return self
+                .get_read_lock()
+                .await
+                .get_peer_addr()
+                .unwrap_or(DEFAULT_SOCKET_ADDR);
         }
         let socket_addr: SocketAddr = stream_result
             .unwrap()
@@ -71,11 +81,19 @@ impl Context {
     }
 
     pub async fn get_socket_addr_string(&self) -> OptionString {
+        let peer_description: OptionString =
+            self.get_read_lock().await.get_peer_description().clone();
+        if peer_description.is_some() {
+            return peer_description;
+        }
         self.get_socket_addr().await.map(|data| data.to_string())
     }
 
     pub async fn get_socket_addr_or_default_string(&self) -> String {
-        self.get_socket_addr_or_default().await.to_string()
+        match self.get_socket_addr_string().await {
+            Some(socket_addr) => socket_addr,
+            None => DEFAULT_SOCKET_ADDR.to_string(),
+        }
     }
 
     pub async fn get_socket_host(&self) -> OptionSocketHost {
@@ -124,6 +142,34 @@ impl Context {
         ctx.get_request().get_upgrade_type().is_websocket()
     }
 
+    async fn inner_is_head_request(&self) -> bool {
+        self.get_request_method().await.to_string() == HEAD
+    }
+
+    async fn inner_send_head_response<T>(
+        &self,
+        status_code: usize,
+        response_body: T,
+    ) -> ResponseResult
+    where
+        T: Into<ResponseBody>,
+    {
+        let body: ResponseBody = response_body.into();
+        let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
+        let has_content_length: bool = ctx
+            .get_response()
+            .get_headers()
+            .keys()
+            .any(|key: &String| key.eq_ignore_ascii_case(CONTENT_LENGTH));
+        if !has_content_length {
+            ctx.get_mut_response()
+                .set_header(CONTENT_LENGTH, body.len().to_string());
+        }
+        ctx.get_mut_response().set_body(body);
+        drop(ctx);
+        self.send_response_head(status_code).await
+    }
+
     async fn inner_send_response<T>(
         &self,
         status_code: usize,
@@ -133,21 +179,152 @@ impl Context {
     where
         T: Into<ResponseBody>,
     {
+        if self.inner_is_head_request().await {
+            return self
+                .inner_send_head_response(status_code, response_body)
+                .await;
+        }
         if let Some(stream_lock) = self.get_stream().await {
             let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
             if !handle_websocket && self.inner_is_websocket(&ctx) {
                 return Err(ResponseError::NotSupportUseThisMethod);
             }
             let body: ResponseBody = response_body.into();
-            let response_res: ResponseResult = ctx
-                .get_mut_response()
-                .set_body(body)
-                .set_status_code(status_code)
-                .send(&stream_lock)
-                .await;
+            let write_timeout: OptionDuration = *ctx.get_write_timeout();
+            let response_res: ResponseResult = Self::inner_write_with_timeout(
+                write_timeout,
+                ctx.get_mut_response()
+                    .set_body(body)
+                    .set_status_code(status_code)
+                    .send(&stream_lock),
+            )
+            .await;
+            ctx.set_responded(true);
             return response_res;
         }
-        Err(ResponseError::NotFoundStream)
+        if !handle_websocket && self.is_enable_websocket().await {
+            return Err(ResponseError::NotSupportUseThisMethod);
+        }
+        self.inner_send_detached_response(status_code, response_body)
+            .await
+    }
+
+    async fn inner_write_with_timeout<F>(write_timeout: OptionDuration, write: F) -> ResponseResult
+    where
+        F: Future<Output = ResponseResult>,
+    {
+        match write_timeout {
+            Some(write_timeout) => timeout(write_timeout, write)
+                .await
+                .unwrap_or(Err(ResponseError::Unknown)),
+            None => write.await,
+        }
+    }
+
+    async fn inner_send_detached_response<T>(
+        &self,
+        status_code: usize,
+        response_body: T,
+    ) -> ResponseResult
+    where
+        T: Into<ResponseBody>,
+    {
+        let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
+        if !*ctx.get_detached() {
+            return Err(ResponseError::NotFoundStream);
+        }
+        let body: ResponseBody = response_body.into();
+        ctx.get_mut_response()
+            .set_body(body.clone())
+            .set_status_code(status_code);
+        ctx.set_responded(true);
+        let Some(response_sender) = ctx.get_response_sender().clone() else {
+            return Ok(());
+        };
+        let headers: ResponseHeaders = ctx.get_response().get_headers().clone();
+        let write_timeout: OptionDuration = *ctx.get_write_timeout();
+        drop(ctx);
+        Self::inner_write_with_timeout(write_timeout, async {
+            Self::inner_send_frame(&response_sender, ResponseFrame::Head(status_code, headers))
+                .await?;
+            Self::inner_send_frame(&response_sender, ResponseFrame::Body(body)).await
+        })
+        .await
+    }
+
+    async fn inner_send_frame(
+        response_sender: &ResponseFrameSender,
+        response_frame: ResponseFrame,
+    ) -> ResponseResult {
+        response_sender
+            .send(response_frame)
+            .await
+            .map_err(|_| ResponseError::Unknown)
+    }
+
+    async fn inner_send_detached_body<T>(&self, response_body: T) -> ResponseResult
+    where
+        T: Into<ResponseBody>,
+    {
+        let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
+        if !*ctx.get_detached() {
+            return Err(ResponseError::NotFoundStream);
+        }
+        let mut body: ResponseBody = response_body.into();
+        if let Some(response_sender) = ctx.get_response_sender().clone() {
+            let status_code: ResponseStatusCode = ctx.get_response().get_status_code().clone();
+            let headers: ResponseHeaders = ctx.get_response().get_headers().clone();
+            let write_timeout: OptionDuration = *ctx.get_write_timeout();
+            ctx.get_mut_response().set_body(body.clone());
+            ctx.set_responded(true);
+            drop(ctx);
+            return Self::inner_write_with_timeout(write_timeout, async {
+                Self::inner_send_frame(&response_sender, ResponseFrame::Head(status_code, headers))
+                    .await?;
+                Self::inner_send_frame(&response_sender, ResponseFrame::Body(body)).await
+            })
+            .await;
+        }
+        if *ctx.get_responded() {
+            let mut sent_body: ResponseBody = ctx.get_response().get_body().clone();
+            sent_body.append(&mut body);
+            body = sent_body;
+        }
+        ctx.get_mut_response().set_body(body);
+        ctx.set_responded(true);
+        Ok(())
+    }
+
+    pub(crate) async fn send_response_head(
+        &self,
+        status_code: ResponseStatusCode,
+    ) -> ResponseResult {
+        let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
+        ctx.get_mut_response().set_status_code(status_code);
+        ctx.set_responded(true);
+        let headers: ResponseHeaders = ctx.get_response().get_headers().clone();
+        let write_timeout: OptionDuration = *ctx.get_write_timeout();
+        if let Some(stream_lock) = ctx.get_stream().clone() {
+            let mut head: Response = Response::default();
+            return Self::inner_write_with_timeout(
+                write_timeout,
+                head.set_body(format_response_head(status_code, &headers))
+                    .send_body_with_websocket_flag(&stream_lock, false),
+            )
+            .await;
+        }
+        let Some(response_sender) = ctx.get_response_sender().clone() else {
+            if *ctx.get_detached() {
+                return Ok(());
+            }
+            return Err(ResponseError::NotFoundStream);
+        };
+        drop(ctx);
+        Self::inner_write_with_timeout(
+            write_timeout,
+            Self::inner_send_frame(&response_sender, ResponseFrame::Head(status_code, headers)),
+        )
+        .await
     }
 
     pub async fn send_response<T>(&self, status_code: usize, response_body: T) -> ResponseResult
@@ -172,22 +349,38 @@ impl Context {
     where
         T: Into<ResponseBody>,
     {
+        if self.inner_is_head_request().await {
+            let response_res: ResponseResult = self
+                .inner_send_head_response(status_code, response_body)
+                .await;
+            let _ = self.close().await;
+            return response_res;
+        }
         if let Some(stream_lock) = self.get_stream().await {
             let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
             if self.inner_is_websocket(&ctx) {
                 return Err(ResponseError::NotSupportUseThisMethod);
             }
+            let write_timeout: OptionDuration = *ctx.get_write_timeout();
             let response: &mut Response = ctx.get_mut_response();
             let body: ResponseBody = response_body.into();
-            let response_res: ResponseResult = response
-                .set_body(body)
-                .set_status_code(status_code)
-                .send(&stream_lock)
-                .await;
+            let response_res: ResponseResult = Self::inner_write_with_timeout(
+                write_timeout,
+                response
+                    .set_body(body)
+                    .set_status_code(status_code)
+                    .send(&stream_lock),
+            )
+            .await;
             let _ = response.close(&stream_lock).await;
+            ctx.set_responded(true);
             return response_res;
         }
-        Err(ResponseError::NotFoundStream)
+        if self.is_enable_websocket().await {
+            return Err(ResponseError::NotSupportUseThisMethod);
+        }
+        self.inner_send_detached_response(status_code, response_body)
+            .await
     }
 
     pub async fn send_once(&self) -> ResponseResult {
@@ -200,18 +393,28 @@ impl Context {
     where
         T: Into<ResponseBody>,
     {
+        if self.inner_is_head_request().await {
+            if self.get_responded().await {
+                return Ok(());
+            }
+            let status_code: ResponseStatusCode = self.get_response_status_code().await;
+            return self.send_response_head(status_code).await;
+        }
         if let Some(stream_lock) = self.get_stream().await {
             let is_websocket: bool = self.get_request_upgrade_type().await.is_websocket();
-            let response_res: ResponseResult = self
-                .get_write_lock()
-                .await
-                .get_mut_response()
-                .set_body(response_body)
-                .send_body_with_websocket_flag(&stream_lock, is_websocket)
-                .await;
+            let mut ctx: RwLockWriteInnerContext = self.get_write_lock().await;
+            let write_timeout: OptionDuration = *ctx.get_write_timeout();
+            let response_res: ResponseResult = Self::inner_write_with_timeout(
+                write_timeout,
+                ctx.get_mut_response()
+                    .set_body(response_body)
+                    .send_body_with_websocket_flag(&stream_lock, is_websocket),
+            )
+            .await;
+            ctx.set_responded(true);
             return response_res;
         }
-        Err(ResponseError::NotFoundStream)
+        self.inner_send_detached_body(response_body).await
     }
 
     pub async fn send_body(&self) -> ResponseResult {
@@ -228,6 +431,12 @@ impl Context {
                 .close(&stream_lock)
                 .await;
         }
+        if let Some(response_sender) = self.get_read_lock().await.get_response_sender().clone() {
+            return Self::inner_send_frame(&response_sender, ResponseFrame::End).await;
+        }
+        if self.get_detached().await {
+            return Ok(());
+        }
         Err(ResponseError::NotFoundStream)
     }
 
@@ -240,6 +449,9 @@ impl Context {
                 .flush(&stream_lock)
                 .await;
         }
+        if self.get_detached().await {
+            return Ok(());
+        }
         Err(ResponseError::NotFoundStream)
     }
 
@@ -610,6 +822,128 @@ impl Context {
         self
     }
 
+    pub async fn get_responded(&self) -> bool {
+        *self.get_read_lock().await.get_responded()
+    }
+
+    pub async fn get_auto_send(&self) -> bool {
+        *self.get_read_lock().await.get_auto_send()
+    }
+
+    pub async fn get_request_id(&self) -> u64 {
+        *self.get_read_lock().await.get_request_id()
+    }
+
+    pub(crate) async fn set_request_id(&self, request_id: u64) -> &Self {
+        self.get_write_lock().await.set_request_id(request_id);
+        self
+    }
+
+    pub async fn get_error_source(&self) -> ErrorSource {
+        self.get_read_lock().await.get_error_source().clone()
+    }
+
+    pub(crate) async fn set_error_source(&self, error_source: ErrorSource) -> &Self {
+        self.get_write_lock().await.set_error_source(error_source);
+        self
+    }
+
+    pub async fn get_listener(&self) -> String {
+        self.get_read_lock().await.get_listener().clone()
+    }
+
+    pub(crate) async fn set_listener<T>(&self, listener: T) -> &Self
+    where
+        T: Into<String>,
+    {
+        self.get_write_lock().await.set_listener(listener.into());
+        self
+    }
+
+    pub(crate) async fn set_write_timeout(&self, write_timeout: OptionDuration) -> &Self {
+        self.get_write_lock().await.set_write_timeout(write_timeout);
+        self
+    }
+
+    pub(crate) async fn set_response_sender(
+        &self,
+        response_sender: OptionResponseFrameSender,
+    ) -> &Self {
+        self.get_write_lock()
+            .await
+            .set_response_sender(response_sender);
+        self
+    }
+
+    pub(crate) async fn set_peer_addr(&self, peer_addr: OptionSocketAddr) -> &Self {
+        self.get_write_lock().await.set_peer_addr(peer_addr);
+        self
+    }
+
+    pub(crate) async fn set_peer_description(&self, peer_description: OptionString) -> &Self {
+        self.get_write_lock()
+            .await
+            .set_peer_description(peer_description);
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn get_tls_alpn_protocol(&self) -> OptionString {
+        self.get_read_lock().await.get_tls_alpn_protocol().clone()
+    }
+
+    #[cfg(feature = "tls")]
+    pub(crate) async fn set_tls_alpn_protocol(&self, tls_alpn_protocol: OptionString) -> &Self {
+        self.get_write_lock()
+            .await
+            .set_tls_alpn_protocol(tls_alpn_protocol);
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn get_tls_peer_certificate(&self) -> Option<Vec<u8>> {
+        self.get_read_lock()
+            .await
+            .get_tls_peer_certificates()
+            .first()
+            .cloned()
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn get_tls_peer_certificates(&self) -> Vec<Vec<u8>> {
+        self.get_read_lock()
+            .await
+            .get_tls_peer_certificates()
+            .clone()
+    }
+
+    #[cfg(feature = "tls")]
+    pub(crate) async fn set_tls_peer_certificates(&self, tls_peer_certificates: VecVecU8) -> &Self {
+        self.get_write_lock()
+            .await
+            .set_tls_peer_certificates(tls_peer_certificates);
+        self
+    }
+
+    pub async fn get_detached(&self) -> bool {
+        *self.get_read_lock().await.get_detached()
+    }
+
+    pub(crate) async fn set_auto_send(&self, auto_send: bool) -> &Self {
+        self.get_write_lock().await.set_auto_send(auto_send);
+        self
+    }
+
+    pub(crate) async fn send_if_auto_send(&self) -> ResponseResult {
+        if !self.get_auto_send().await || self.get_responded().await {
+            return Ok(());
+        }
+        if self.is_enable_websocket().await {
+            return self.send_body().await;
+        }
+        self.send().await
+    }
+
     pub async fn http_request_from_stream(&self, buffer_size: usize) -> RequestReaderHandleResult {
         if let Some(stream) = self.get_stream().await.as_ref() {
             return Request::http_request_from_stream(stream, buffer_size).await;
@@ -8,6 +8,22 @@ pub struct InnerContext {
     attribute: HashMapArcAnySendSync,
     route_params: ArcRwLockRouteParams,
     aborted: bool,
+    responded: bool,
+    auto_send: bool,
+    detached: bool,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    response_sender: OptionResponseFrameSender,
+    peer_addr: OptionSocketAddr,
+    peer_description: OptionString,
+    #[cfg(feature = "tls")]
+    tls_alpn_protocol: OptionString,
+    #[cfg(feature = "tls")]
+    tls_peer_certificates: VecVecU8,
+    listener: String,
+    write_timeout: OptionDuration,
+    request_id: u64,
+    error_source: ErrorSource,
 }
 
 #[derive(Clone, Default)]
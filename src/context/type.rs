@@ -3,3 +3,7 @@ use crate::*;
 pub type RwLockWriteInnerContext<'a> = RwLockWriteGuard<'a, InnerContext>;
 pub type RwLockReadInnerContext<'a> = RwLockReadGuard<'a, InnerContext>;
 pub type HashMapArcAnySendSync = HashMap<String, ArcAnySendSync>;
+
+pub(crate) type ResponseFrameSender = mpsc::Sender<ResponseFrame>;
+pub(crate) type OptionResponseFrameSender = Option<ResponseFrameSender>;
+pub(crate) type ResponseFrameReceiver = mpsc::Receiver<ResponseFrame>;
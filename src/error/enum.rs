@@ -1,14 +1,50 @@
 use crate::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ServerError {
     TcpBindError(String),
+    AcceptError(String),
     HttpReadError(String),
     InvalidHttpRequest(RequestError),
+    TlsError(String),
     Unknown,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractError {
+    InvalidPath(String),
+    InvalidQuery(String),
+    InvalidJsonSyntax(String),
+    InvalidJsonData(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ErrorSource {
+    #[default]
+    Server,
+    RequestMiddleware(usize),
+    Middleware(usize),
+    RouterMiddleware(String, usize),
+    RouteMiddleware(usize),
+    Handler(String),
+    Fallback(String),
+    ResponseMiddleware(usize),
+}
+
+#[derive(Clone, Debug)]
+pub enum ErrorEvent {
+    Bind(String, String),
+    Accept(String, String),
+    Read(String, OptionSocketAddr, String),
+    Write(ErrorRequest, String),
+    Panic(ErrorRequest, RequestPanic),
+    HandlerTimeout(ErrorRequest),
+}
+
 #[derive(Debug)]
 pub(crate) enum RouteError {
     DuplicatePattern(String),
+    DuplicateMethod(String, String),
+    ConflictingParams(String),
+    InvalidPattern(String),
 }
@@ -6,15 +6,128 @@ impl Display for ServerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::TcpBindError(data) => write!(f, "Tcp bind error{}{}", COLON_SPACE, data),
+            Self::AcceptError(data) => write!(f, "Accept error{}{}", COLON_SPACE, data),
             Self::HttpReadError(data) => write!(f, "Http read error{}{}", COLON_SPACE, data),
             Self::InvalidHttpRequest(data) => {
                 write!(f, "Invalid http request{}{}", COLON_SPACE, data)
             }
+            Self::TlsError(data) => write!(f, "Tls error{}{}", COLON_SPACE, data),
             Self::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+impl StdError for ExtractError {}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPath(data) => write!(f, "Invalid path params{}{}", COLON_SPACE, data),
+            Self::InvalidQuery(data) => write!(f, "Invalid query{}{}", COLON_SPACE, data),
+            Self::InvalidJsonSyntax(data) => {
+                write!(f, "Invalid json body syntax{}{}", COLON_SPACE, data)
+            }
+            Self::InvalidJsonData(data) => {
+                write!(f, "Invalid json body data{}{}", COLON_SPACE, data)
+            }
+        }
+    }
+}
+
+impl ExtractError {
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            Self::InvalidPath(_) => "invalid_path",
+            Self::InvalidQuery(_) => "invalid_query",
+            Self::InvalidJsonSyntax(_) => "invalid_json_syntax",
+            Self::InvalidJsonData(_) => "invalid_json_data",
+        }
+    }
+
+    pub fn get_status_code(&self) -> ResponseStatusCode {
+        match self {
+            Self::InvalidJsonData(_) => 422,
+            _ => 400,
+        }
+    }
+}
+
+impl ErrorRequest {
+    pub(crate) async fn from_context(ctx: &Context) -> Self {
+        Self {
+            context: ctx.clone(),
+            method: ctx.get_request_method().await.to_string(),
+            path: ctx.get_request_path().await,
+            peer: ctx.get_socket_addr().await,
+            request_id: ctx.get_request_id().await,
+            source: ctx.get_error_source().await,
+        }
+    }
+}
+
+impl fmt::Debug for ErrorRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorRequest")
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .field("peer", &self.peer)
+            .field("request_id", &self.request_id)
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for ErrorRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let peer: String = self
+            .peer
+            .map(|peer: SocketAddr| peer.to_string())
+            .unwrap_or_default();
+        write!(
+            f,
+            "#{} {} {} from {} in {:?}",
+            self.request_id, self.method, self.path, peer, self.source
+        )
+    }
+}
+
+impl ErrorEvent {
+    pub fn get_request(&self) -> Option<&ErrorRequest> {
+        match self {
+            Self::Write(request, _) | Self::Panic(request, _) | Self::HandlerTimeout(request) => {
+                Some(request)
+            }
+            Self::Bind(..) | Self::Accept(..) | Self::Read(..) => None,
+        }
+    }
+}
+
+impl Display for ErrorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bind(addr, data) => write!(f, "Bind error {}{}{}", addr, COLON_SPACE, data),
+            Self::Accept(listener, data) => {
+                write!(f, "Accept error on {}{}{}", listener, COLON_SPACE, data)
+            }
+            Self::Read(listener, peer, data) => {
+                let peer: String = peer
+                    .map(|peer: SocketAddr| peer.to_string())
+                    .unwrap_or_default();
+                write!(
+                    f,
+                    "Read error on {} from {}{}{}",
+                    listener, peer, COLON_SPACE, data
+                )
+            }
+            Self::Write(request, data) => {
+                write!(f, "Write error {}{}{}", request, COLON_SPACE, data)
+            }
+            Self::Panic(request, panic) => write!(f, "Panic {}{}{}", request, COLON_SPACE, panic),
+            Self::HandlerTimeout(request) => write!(f, "Handler timeout {}", request),
+        }
+    }
+}
+
 impl StdError for RouteError {}
 
 impl Display for RouteError {
@@ -23,6 +136,19 @@ impl Display for RouteError {
             Self::DuplicatePattern(pattern) => {
                 write!(f, "Route pattern already exists: {}", pattern)
             }
+            Self::DuplicateMethod(method, pattern) => {
+                write!(f, "Route method already exists: {} {}", method, pattern)
+            }
+            Self::ConflictingParams(pattern) => {
+                write!(
+                    f,
+                    "Route params conflict with an existing route: {}",
+                    pattern
+                )
+            }
+            Self::InvalidPattern(pattern) => {
+                write!(f, "Route pattern is invalid: {}", pattern)
+            }
         }
     }
 }
@@ -0,0 +1,17 @@
+use crate::*;
+
+#[derive(Clone, Getter)]
+pub struct ErrorRequest {
+    #[get(pub)]
+    pub(super) context: Context,
+    #[get(pub)]
+    pub(super) method: String,
+    #[get(pub)]
+    pub(super) path: String,
+    #[get(pub)]
+    pub(super) peer: OptionSocketAddr,
+    #[get(pub)]
+    pub(super) request_id: u64,
+    #[get(pub)]
+    pub(super) source: ErrorSource,
+}
@@ -0,0 +1,31 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_from_context() {
+    let ctx: Context = Context::default();
+    let mut params: RouteParams = hash_map_xx_hash3_64();
+    params.insert("id".to_string(), "42".to_string());
+    ctx.set_route_params(params).await;
+    ctx.set_request_query("page", "3").await;
+    ctx.set_request_body(r#"{"name":"hyperlane"}"#).await;
+    let Path(id) = Path::<u64>::from_context(ctx.clone()).await.unwrap();
+    assert_eq!(id, 42);
+    let Query(querys) = Query::<HashMap<String, usize>>::from_context(ctx.clone())
+        .await
+        .unwrap();
+    assert_eq!(querys.get("page"), Some(&3));
+    let Json(body) = Json::<HashMap<String, String>>::from_context(ctx.clone())
+        .await
+        .unwrap();
+    assert_eq!(body.get("name"), Some(&"hyperlane".to_string()));
+    let error: ExtractError = Json::<Vec<u8>>::from_context(ctx.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(error.get_status_code(), 422);
+    ctx.set_request_body("{").await;
+    let error: ExtractError = Json::<Vec<u8>>::from_context(ctx.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(error.get_status_code(), 400);
+    assert!(Path::<bool>::from_context(ctx).await.is_err());
+}
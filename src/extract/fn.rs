@@ -0,0 +1,17 @@
+use crate::*;
+
+pub(crate) fn deserialize_string_map<T, M>(map: &M) -> Result<T, String>
+where
+    T: DeserializeOwned,
+    M: Serialize,
+{
+    let encoded: String = serde_urlencoded::to_string(map).map_err(|err| err.to_string())?;
+    let map_error: String = match serde_urlencoded::from_str::<T>(&encoded) {
+        Ok(data) => return Ok(data),
+        Err(err) => err.to_string(),
+    };
+    match serde_urlencoded::from_str::<Vec<(String, T)>>(&encoded) {
+        Ok(mut list) if list.len() == 1 => Ok(list.remove(0).1),
+        _ => Err(map_error),
+    }
+}
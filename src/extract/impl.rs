@@ -0,0 +1,50 @@
+use crate::*;
+
+impl<T> FromContext for Json<T>
+where
+    T: DeserializeOwned + Send,
+{
+    async fn from_context(ctx: Context) -> Result<Self, ExtractError> {
+        ctx.get_request_body_json::<T>()
+            .await
+            .map(Json)
+            .map_err(|err| match err.is_data() {
+                true => ExtractError::InvalidJsonData(err.to_string()),
+                false => ExtractError::InvalidJsonSyntax(err.to_string()),
+            })
+    }
+}
+
+impl<T> FromContext for Path<T>
+where
+    T: DeserializeOwned + Send,
+{
+    async fn from_context(ctx: Context) -> Result<Self, ExtractError> {
+        let params: RouteParams = ctx.get_route_params().await;
+        deserialize_string_map(&params)
+            .map(Path)
+            .map_err(ExtractError::InvalidPath)
+    }
+}
+
+impl<T> FromContext for Query<T>
+where
+    T: DeserializeOwned + Send,
+{
+    async fn from_context(ctx: Context) -> Result<Self, ExtractError> {
+        let querys: RequestQuerys = ctx.get_request_querys().await;
+        deserialize_string_map(&querys)
+            .map(Query)
+            .map_err(ExtractError::InvalidQuery)
+    }
+}
+
+impl IntoResponse for ExtractError {
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        let body: serde_json::Value = serde_json::json!({
+            "error": self.get_kind(),
+            "message": self.to_string(),
+        });
+        (self.get_status_code(), Json(body)).into_response(ctx)
+    }
+}
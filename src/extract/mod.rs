@@ -0,0 +1,10 @@
+pub(crate) mod cfg;
+pub(crate) mod r#fn;
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#trait;
+
+pub use r#struct::*;
+pub use r#trait::*;
+
+pub(crate) use r#fn::*;
@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, Default)]
+pub struct Path<T>(pub T);
+
+#[derive(Debug, Clone, Default)]
+pub struct Query<T>(pub T);
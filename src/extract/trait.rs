@@ -0,0 +1,5 @@
+use crate::*;
+
+pub trait FromContext: Sized {
+    fn from_context(ctx: Context) -> impl Future<Output = Result<Self, ExtractError>> + Send;
+}
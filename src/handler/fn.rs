@@ -1,4 +1,8 @@
-pub(crate) fn print_error_handle(error: String) {
-    eprintln!("{}", error);
-    let _ = std::io::Write::flush(&mut std::io::stderr());
+use crate::*;
+
+pub(crate) fn print_error_handle(event: ErrorEvent) -> PinBoxFutureSend {
+    Box::pin(async move {
+        eprintln!("{}", event);
+        let _ = std::io::Write::flush(&mut std::io::stderr());
+    })
 }
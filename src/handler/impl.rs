@@ -1,12 +1,95 @@
 use crate::*;
 
-impl<T> ErrorHandle for T where T: Fn(String) {}
+impl<F> ErrorHandle for F where
+    F: Fn(ErrorEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync
+{
+}
+
+impl<F, Fut> ErrorHandleWithoutPin<Fut> for F
+where
+    F: Fn(ErrorEvent) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+}
 
 impl<F> Func for F where F: Fn(Context) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync {}
 
 impl<F, Fut> FuncWithoutPin<Fut> for F
 where
     F: Fn(Context) -> Fut + Send + Sync + 'static,
+    Fut: Future + Send,
+{
+}
+
+impl<F> MiddlewareFunc for F where
+    F: Fn(Context, Next) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync
+{
+}
+
+impl<F, Fut> MiddlewareFuncWithoutPin<Fut> for F
+where
+    F: Fn(Context, Next) -> Fut + Send + Sync + 'static,
     Fut: Future<Output = ()> + Send,
 {
 }
+
+impl<F, Fut> RouteFunc<()> for F
+where
+    F: FuncWithoutPin<Fut>,
+    Fut: Future + Send + 'static,
+    Fut::Output: IntoResponse,
+{
+    fn into_route_func(self) -> ArcFunc {
+        Arc::new(move |ctx: Context| {
+            let future: Fut = self(ctx.clone());
+            Box::pin(async move {
+                future.await.into_response(ctx).await;
+            }) as PinBoxFutureSend
+        })
+    }
+}
+
+macro_rules! impl_route_func {
+    ($($param:ident),+) => {
+        impl<F, Fut, $($param,)+> RouteFunc<($($param,)+)> for F
+        where
+            F: Fn(Context, $($param,)+) -> Fut + Send + Sync + 'static,
+            Fut: Future + Send + 'static,
+            Fut::Output: IntoResponse,
+            $($param: FromContext + Send + 'static,)+
+        {
+            #[allow(non_snake_case)]
+            fn into_route_func(self) -> ArcFunc {
+                let func: Arc<F> = Arc::new(self);
+                Arc::new(move |ctx: Context| {
+                    let func: Arc<F> = func.clone();
+                    Box::pin(async move {
+                        $(
+                            let $param: $param =
+                                match <$param as FromContext>::from_context(ctx.clone()).await {
+                                    Ok(data) => data,
+                                    Err(err) => {
+                                        err.into_response(ctx).await;
+                                        return;
+                                    }
+                                };
+                        )+
+                        func(ctx.clone(), $($param,)+)
+                            .await
+                            .into_response(ctx)
+                            .await;
+                    }) as PinBoxFutureSend
+                })
+            }
+        }
+    };
+}
+
+impl_route_func!(T1);
+impl_route_func!(T1, T2);
+impl_route_func!(T1, T2, T3);
+impl_route_func!(T1, T2, T3, T4);
+impl_route_func!(T1, T2, T3, T4, T5);
+impl_route_func!(T1, T2, T3, T4, T5, T6);
+impl_route_func!(T1, T2, T3, T4, T5, T6, T7);
+impl_route_func!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -1,11 +1,35 @@
 use crate::*;
 
-pub trait ErrorHandle: Fn(String) {}
+pub trait ErrorHandle:
+    Fn(ErrorEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync
+{
+}
+
+pub trait ErrorHandleWithoutPin<Fut>: Fn(ErrorEvent) -> Fut + Send + Sync + 'static
+where
+    Fut: Future<Output = ()> + Send,
+{
+}
 
 pub trait Func: Fn(Context) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync {}
 
 pub trait FuncWithoutPin<Fut>: Fn(Context) -> Fut + Send + Sync + 'static
+where
+    Fut: Future + Send,
+{
+}
+
+pub trait MiddlewareFunc:
+    Fn(Context, Next) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync
+{
+}
+
+pub trait MiddlewareFuncWithoutPin<Fut>: Fn(Context, Next) -> Fut + Send + Sync + 'static
 where
     Fut: Future<Output = ()> + Send,
 {
 }
+
+pub trait RouteFunc<Args>: Send + Sync + 'static {
+    fn into_route_func(self) -> ArcFunc;
+}
@@ -1,39 +1,121 @@
 pub(crate) mod cfg;
+pub(crate) mod client;
 pub(crate) mod config;
 pub(crate) mod context;
 pub(crate) mod error;
+pub(crate) mod extract;
 pub(crate) mod handler;
 pub(crate) mod middleware;
+pub(crate) mod panic;
+pub(crate) mod response;
 pub(crate) mod route;
+pub(crate) mod router;
 pub(crate) mod server;
+pub(crate) mod static_file;
+#[cfg(feature = "tls")]
+pub(crate) mod tls;
 
+pub use client::*;
 pub use context::*;
 pub use error::*;
+pub use extract::*;
 pub use handler::*;
+pub use middleware::*;
+pub use panic::*;
+pub use response::*;
+pub use router::*;
 pub use server::*;
+pub use static_file::*;
 
 pub use http_type::*;
 
 pub(crate) use config::*;
 pub(crate) use lombok_macros::*;
-pub(crate) use middleware::*;
 pub(crate) use route::*;
+#[cfg(feature = "tls")]
+pub(crate) use tls::*;
+
+#[cfg(feature = "http2")]
+pub(crate) use bytes::Bytes;
+#[cfg(feature = "http2")]
+pub(crate) use h2::{
+    RecvStream, SendStream,
+    server::{Builder as Http2Builder, SendResponse},
+};
+#[cfg(feature = "http2")]
+pub(crate) use http::{Request as Http2Request, Response as Http2Response};
+#[cfg(feature = "tls")]
+pub(crate) use rustls::{
+    RootCertStore, ServerConfig as RustlsServerConfig,
+    crypto::{CryptoProvider, ring::default_provider, ring::sign::any_supported_type},
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier},
+    sign::CertifiedKey,
+};
+#[cfg(feature = "http2")]
+pub(crate) use std::future::poll_fn;
+#[cfg(feature = "tls")]
+pub(crate) use std::sync::{PoisonError, RwLock as StdRwLock, atomic::AtomicBool};
+#[cfg(feature = "tls")]
+pub(crate) use tokio_rustls::{TlsAcceptor, server::TlsStream as ServerTlsStream};
 
 pub(crate) use core::hash::BuildHasherDefault;
-pub(crate) use serde::de::DeserializeOwned;
+#[cfg(unix)]
+pub(crate) use libc::{c_int, c_void, sockaddr, sockaddr_storage, socklen_t};
+pub(crate) use regex::Regex;
+pub(crate) use serde::{Serialize, de::DeserializeOwned};
 pub(crate) use std::{
+    any::Any,
+    backtrace::Backtrace,
+    borrow::Cow,
+    cell::{Cell, RefCell},
     collections::HashMap,
     error::Error as StdError,
+    ffi::OsString,
     fmt::{self, Display},
+    fs::Metadata,
     future::Future,
-    net::SocketAddr,
-    panic::{PanicHookInfo, set_hook},
+    io::{Error as IoError, ErrorKind, Result as IoResult, SeekFrom},
+    mem::take,
+    net::{SocketAddr, TcpListener as StdTcpListener},
+    panic::{AssertUnwindSafe, PanicHookInfo, catch_unwind, set_hook, take_hook},
+    path::{Path, PathBuf},
     pin::Pin,
-    sync::Arc,
-    time::Duration,
+    sync::{
+        Arc, Once, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::{Context as TaskContext, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+#[cfg(unix)]
+pub(crate) use std::{
+    env,
+    fs::{Permissions, remove_file, set_permissions, symlink_metadata},
+    os::{
+        fd::{FromRawFd, RawFd},
+        unix::{
+            fs::{FileTypeExt, PermissionsExt, chown},
+            net::UnixStream as StdUnixStream,
+        },
+    },
+    process,
 };
+#[cfg(unix)]
+pub(crate) use tokio::net::{UnixListener, UnixStream};
 pub(crate) use tokio::{
-    net::TcpListener,
-    sync::{RwLockReadGuard, RwLockWriteGuard},
-    task::yield_now,
+    fs,
+    io::{
+        AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWrite, AsyncWriteExt, DuplexStream, ReadBuf,
+        ReadHalf, WriteHalf, duplex, split,
+    },
+    join,
+    net::{TcpListener, TcpStream},
+    select, spawn,
+    sync::{
+        OwnedSemaphorePermit, RwLockReadGuard, RwLockWriteGuard, Semaphore, TryAcquireError, mpsc,
+        watch,
+    },
+    task::{JoinSet, yield_now},
+    time::{sleep, timeout},
 };
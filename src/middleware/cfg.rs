@@ -0,0 +1,46 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_next_onion_order() {
+    let order: Arc<std::sync::Mutex<Vec<&'static str>>> = Arc::new(std::sync::Mutex::new(vec![]));
+    let outer_order: Arc<std::sync::Mutex<Vec<&'static str>>> = order.clone();
+    let outer: ArcMiddlewareFunc = Arc::new(move |ctx: Context, next: Next| {
+        let order: Arc<std::sync::Mutex<Vec<&'static str>>> = outer_order.clone();
+        Box::pin(async move {
+            order.lock().unwrap().push("outer before");
+            next.run(ctx).await;
+            order.lock().unwrap().push("outer after");
+        }) as PinBoxFutureSend
+    });
+    let inner_order: Arc<std::sync::Mutex<Vec<&'static str>>> = order.clone();
+    let inner: ArcMiddlewareFunc = Arc::new(move |ctx: Context, next: Next| {
+        let order: Arc<std::sync::Mutex<Vec<&'static str>>> = inner_order.clone();
+        Box::pin(async move {
+            order.lock().unwrap().push("inner before");
+            next.run(ctx.clone()).await;
+            next.run(ctx).await;
+            order.lock().unwrap().push("inner after");
+        }) as PinBoxFutureSend
+    });
+    let endpoint_order: Arc<std::sync::Mutex<Vec<&'static str>>> = order.clone();
+    let endpoint: ArcFunc = Arc::new(move |_: Context| {
+        let order: Arc<std::sync::Mutex<Vec<&'static str>>> = endpoint_order.clone();
+        Box::pin(async move {
+            order.lock().unwrap().push("endpoint");
+        }) as PinBoxFutureSend
+    });
+    Next::new(Arc::new(vec![outer, inner]), endpoint)
+        .run(Context::default())
+        .await;
+    assert_eq!(
+        *order.lock().unwrap(),
+        vec![
+            "outer before",
+            "inner before",
+            "endpoint",
+            "endpoint",
+            "inner after",
+            "outer after"
+        ]
+    );
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+pub(crate) fn request_middleware_layer(
+    request_middleware: ArcRwLockMiddlewareFuncBox,
+) -> ArcMiddlewareFunc {
+    Arc::new(move |ctx: Context, next: Next| {
+        let request_middleware: ArcRwLockMiddlewareFuncBox = request_middleware.clone();
+        Box::pin(async move {
+            for (index, middleware) in request_middleware.read().await.iter().enumerate() {
+                ctx.set_error_source(ErrorSource::RequestMiddleware(index))
+                    .await;
+                middleware(ctx.clone()).await;
+                if ctx.get_aborted().await {
+                    return;
+                }
+            }
+            next.run(ctx).await;
+        }) as PinBoxFutureSend
+    })
+}
+
+pub(crate) fn response_middleware_layer(
+    response_middleware: ArcRwLockMiddlewareFuncBox,
+) -> ArcMiddlewareFunc {
+    Arc::new(move |ctx: Context, next: Next| {
+        let response_middleware: ArcRwLockMiddlewareFuncBox = response_middleware.clone();
+        Box::pin(async move {
+            next.run(ctx.clone()).await;
+            for (index, middleware) in response_middleware.read().await.iter().enumerate() {
+                if ctx.get_aborted().await {
+                    return;
+                }
+                ctx.set_error_source(ErrorSource::ResponseMiddleware(index))
+                    .await;
+                middleware(ctx.clone()).await;
+            }
+        }) as PinBoxFutureSend
+    })
+}
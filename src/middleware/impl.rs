@@ -0,0 +1,27 @@
+use crate::*;
+
+impl Next {
+    pub(crate) fn new(middleware: ArcVecArcMiddlewareFunc, endpoint: ArcFunc) -> Self {
+        Self {
+            middleware,
+            index: 0,
+            endpoint,
+        }
+    }
+
+    pub async fn run(&self, ctx: Context) {
+        let error_source: ErrorSource = ctx.get_error_source().await;
+        match self.middleware.get(self.index) {
+            Some(middleware) => {
+                let next: Next = Next {
+                    middleware: self.middleware.clone(),
+                    index: self.index + 1,
+                    endpoint: self.endpoint.clone(),
+                };
+                middleware(ctx.clone(), next).await;
+            }
+            None => (self.endpoint)(ctx.clone()).await,
+        }
+        ctx.set_error_source(error_source).await;
+    }
+}
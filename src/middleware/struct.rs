@@ -0,0 +1,8 @@
+use crate::*;
+
+#[derive(Clone)]
+pub struct Next {
+    pub(super) middleware: ArcVecArcMiddlewareFunc,
+    pub(super) index: usize,
+    pub(super) endpoint: ArcFunc,
+}
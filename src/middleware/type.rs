@@ -1,3 +1,12 @@
 use crate::*;
 
 pub(crate) type ArcRwLockMiddlewareFuncBox = ArcRwLock<VecArcFunc>;
+pub(crate) type ArcMiddlewareFunc = Arc<dyn MiddlewareFunc>;
+pub(crate) type VecArcMiddlewareFunc = Vec<ArcMiddlewareFunc>;
+pub(crate) type ArcVecArcMiddlewareFunc = Arc<VecArcMiddlewareFunc>;
+pub(crate) type ArcRwLockVecArcMiddlewareFunc = ArcRwLock<VecArcMiddlewareFunc>;
+pub(crate) type RouteMiddleware = (RoutePattern, ArcFunc);
+pub(crate) type VecRouteMiddleware = Vec<RouteMiddleware>;
+pub(crate) type ArcRwLockRouteMiddleware = ArcRwLock<VecRouteMiddleware>;
+pub(crate) type IndexArcFunc = (usize, ArcFunc);
+pub(crate) type VecIndexArcFunc = Vec<IndexArcFunc>;
@@ -0,0 +1,18 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_catch_unwind() {
+    install_panic_hook();
+    let result: ResultPanicPayload<()> = CatchUnwind::new(async {
+        panic!("Test panic");
+    })
+    .await;
+    let payload: PanicPayload = result.unwrap_err();
+    assert_eq!(panic_payload_message(&payload), "Test panic");
+    assert!(!take_panic_backtrace().is_empty());
+    let result: ResultPanicPayload<usize> = CatchUnwind::new(async { 1 }).await;
+    assert_eq!(result.unwrap(), 1);
+    let result: ResultPanicPayload<()> = catch_unwind(|| panic!("Outside a handler"));
+    assert!(result.is_err());
+    assert!(take_panic_backtrace().is_empty());
+}
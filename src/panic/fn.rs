@@ -0,0 +1,49 @@
+use crate::*;
+
+thread_local! {
+    static PANIC_BACKTRACE: RefCellOptionString = const { RefCell::new(None) };
+    static CATCHING_PANIC: CellBool = const { Cell::new(false) };
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+// The hook is chained in front of whatever hook is installed when the server
+// starts. Panics outside a request handler go straight to that hook. Handler
+// panics are caught by CatchUnwind, get a backtrace and stay off stderr. A hook
+// the application installs after start replaces this one, and handler panics
+// then report an empty backtrace.
+pub(crate) fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous_hook: BoxPanicHook = take_hook();
+        set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+            if !CATCHING_PANIC.with(Cell::get) {
+                previous_hook(info);
+                return;
+            }
+            let backtrace: String = Backtrace::force_capture().to_string();
+            PANIC_BACKTRACE.with(|cell: &RefCellOptionString| {
+                *cell.borrow_mut() = Some(backtrace);
+            });
+        }));
+    });
+}
+
+pub(crate) fn set_catching_panic(catching: bool) -> bool {
+    CATCHING_PANIC.with(|cell: &CellBool| cell.replace(catching))
+}
+
+pub(crate) fn take_panic_backtrace() -> String {
+    PANIC_BACKTRACE
+        .with(|cell: &RefCellOptionString| cell.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+pub(crate) fn panic_payload_message(payload: &PanicPayload) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    String::new()
+}
@@ -0,0 +1,47 @@
+use crate::*;
+
+impl<F> CatchUnwind<F>
+where
+    F: Future,
+{
+    pub(crate) fn new(future: F) -> Self {
+        Self {
+            future: Box::pin(future),
+        }
+    }
+}
+
+impl<F> Future for CatchUnwind<F>
+where
+    F: Future,
+{
+    type Output = ResultPanicPayload<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let future: &mut PinBoxFuture<F> = &mut self.future;
+        let was_catching: bool = set_catching_panic(true);
+        let poll_result: ResultPanicPayload<Poll<F::Output>> =
+            catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx)));
+        set_catching_panic(was_catching);
+        match poll_result {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+impl RequestPanic {
+    pub(crate) fn from_payload(payload: &PanicPayload) -> Self {
+        Self {
+            message: panic_payload_message(payload),
+            backtrace: take_panic_backtrace(),
+        }
+    }
+}
+
+impl Display for RequestPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.message, self.backtrace)
+    }
+}
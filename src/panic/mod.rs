@@ -0,0 +1,10 @@
+pub(crate) mod cfg;
+pub(crate) mod r#fn;
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#type;
+
+pub use r#struct::*;
+
+pub(crate) use r#fn::*;
+pub(crate) use r#type::*;
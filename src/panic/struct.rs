@@ -0,0 +1,13 @@
+use crate::*;
+
+#[derive(Debug, Clone, Getter)]
+pub struct RequestPanic {
+    #[get(pub)]
+    pub(super) message: String,
+    #[get(pub)]
+    pub(super) backtrace: String,
+}
+
+pub(crate) struct CatchUnwind<F> {
+    pub(super) future: PinBoxFuture<F>,
+}
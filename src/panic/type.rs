@@ -0,0 +1,8 @@
+use crate::*;
+
+pub(crate) type PinBoxFuture<F> = Pin<Box<F>>;
+pub(crate) type PanicPayload = Box<dyn Any + Send>;
+pub(crate) type ResultPanicPayload<T> = Result<T, PanicPayload>;
+pub(crate) type RefCellOptionString = RefCell<OptionString>;
+pub(crate) type CellBool = Cell<bool>;
+pub(crate) type BoxPanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
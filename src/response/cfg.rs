@@ -0,0 +1,18 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_into_response() {
+    let ctx: Context = Context::default();
+    ().into_response(ctx.clone()).await;
+    assert!(!ctx.get_auto_send().await);
+    "hello".into_response(ctx.clone()).await;
+    assert!(ctx.get_auto_send().await);
+    assert_eq!(ctx.get_response_body_string().await, "hello");
+    let result: Result<String, (ResponseStatusCode, &'static str)> = Err((422, "invalid"));
+    result.into_response(ctx.clone()).await;
+    assert_eq!(ctx.get_response_status_code().await, 422);
+    assert_eq!(ctx.get_response_body_string().await, "invalid");
+    (201, Json(vec![1, 2, 3])).into_response(ctx.clone()).await;
+    assert_eq!(ctx.get_response_status_code().await, 201);
+    assert_eq!(ctx.get_response_body_string().await, "[1,2,3]");
+}
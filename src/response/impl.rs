@@ -0,0 +1,105 @@
+use crate::*;
+
+impl<T> FnPointerOutput for fn() -> T {
+    type Output = T;
+}
+
+impl IntoResponse for Never {
+    fn into_response(self, _: Context) -> PinBoxFutureSend {
+        match self {}
+    }
+}
+
+impl IntoResponse for () {
+    fn into_response(self, _: Context) -> PinBoxFutureSend {
+        Box::pin(async move {})
+    }
+}
+
+impl IntoResponse for &'static str {
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        Box::pin(async move {
+            ctx.set_response_body(self).await.set_auto_send(true).await;
+        })
+    }
+}
+
+impl IntoResponse for String {
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        Box::pin(async move {
+            ctx.set_response_body(self).await.set_auto_send(true).await;
+        })
+    }
+}
+
+impl IntoResponse for Vec<u8> {
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        Box::pin(async move {
+            ctx.set_response_body(self).await.set_auto_send(true).await;
+        })
+    }
+}
+
+impl IntoResponse for Response {
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        Box::pin(async move {
+            ctx.set_response(self).await.set_auto_send(true).await;
+        })
+    }
+}
+
+impl<T> IntoResponse for Json<T>
+where
+    T: Serialize,
+{
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        let body_result: Result<Vec<u8>, serde_json::Error> = serde_json::to_vec(&self.0);
+        Box::pin(async move {
+            match body_result {
+                Ok(body) => {
+                    ctx.set_response_header(CONTENT_TYPE, APPLICATION_JSON)
+                        .await
+                        .set_response_body(body)
+                        .await;
+                }
+                Err(err) => {
+                    ctx.set_response_status_code(500)
+                        .await
+                        .set_response_body(err.to_string())
+                        .await;
+                }
+            }
+            ctx.set_auto_send(true).await;
+        })
+    }
+}
+
+impl<T> IntoResponse for (ResponseStatusCode, T)
+where
+    T: IntoResponse,
+{
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        let (status_code, data) = self;
+        let future: PinBoxFutureSend = data.into_response(ctx.clone());
+        Box::pin(async move {
+            future.await;
+            ctx.set_response_status_code(status_code)
+                .await
+                .set_auto_send(true)
+                .await;
+        })
+    }
+}
+
+impl<T, E> IntoResponse for Result<T, E>
+where
+    T: IntoResponse,
+    E: IntoResponse,
+{
+    fn into_response(self, ctx: Context) -> PinBoxFutureSend {
+        match self {
+            Ok(data) => data.into_response(ctx),
+            Err(err) => err.into_response(ctx),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use crate::*;
+
+pub trait IntoResponse {
+    fn into_response(self, ctx: Context) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+}
+
+pub(crate) trait FnPointerOutput {
+    type Output;
+}
@@ -0,0 +1,7 @@
+use crate::*;
+
+// `!` cannot be named as a type on stable Rust, so it is extracted from a fn pointer's output.
+// Under the edition 2024 never-type fallback, a diverging handler such as
+// `|_| async move { panic!() }` infers its output as `!` rather than `()`, so the never type
+// needs its own `IntoResponse` impl for those handlers to satisfy `RouteFunc`.
+pub(crate) type Never = <fn() -> ! as FnPointerOutput>::Output;
@@ -0,0 +1,233 @@
+use crate::*;
+
+#[test]
+fn test_route_method_resolve() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    route_matcher
+        .add_method("/users/:id", Some(GET.to_string()), handler.clone())
+        .unwrap();
+    route_matcher
+        .add_method("/users/:id", Some(POST.to_string()), handler.clone())
+        .unwrap();
+    assert!(
+        route_matcher
+            .add_method("/users/:name", Some(GET.to_string()), handler.clone())
+            .is_err()
+    );
+    let (route_methods, params) = route_matcher.match_route("/users/1").unwrap();
+    assert_eq!(params.get("id"), Some(&"1".to_string()));
+    assert!(matches!(
+        route_methods.resolve(GET, params.clone()),
+        RouteMatch::Matched(_, _, _)
+    ));
+    assert!(matches!(
+        route_methods.resolve(HEAD, params.clone()),
+        RouteMatch::Matched(_, _, _)
+    ));
+    match route_methods.resolve(DELETE, params) {
+        RouteMatch::MethodNotAllowed(allow) => assert_eq!(allow, "GET, HEAD, POST"),
+        _ => panic!("Expected method not allowed"),
+    }
+    route_matcher
+        .add_method("/users/:id", Some(OPTIONS.to_string()), handler.clone())
+        .unwrap();
+    let (route_methods, params) = route_matcher.match_route("/users/1").unwrap();
+    match route_methods.resolve(DELETE, params) {
+        RouteMatch::MethodNotAllowed(allow) => assert_eq!(allow, "GET, HEAD, OPTIONS, POST"),
+        _ => panic!("Expected method not allowed"),
+    }
+    route_matcher
+        .add_method("/posts", Some(POST.to_string()), handler.clone())
+        .unwrap();
+    let (route_methods, params) = route_matcher.match_route("/posts").unwrap();
+    match route_methods.resolve(HEAD, params) {
+        RouteMatch::MethodNotAllowed(allow) => assert_eq!(allow, "POST"),
+        _ => panic!("Expected method not allowed"),
+    }
+}
+
+#[test]
+fn test_route_method_pattern_per_method() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    route_matcher
+        .add_method("/posts/:year", Some(POST.to_string()), handler.clone())
+        .unwrap();
+    route_matcher
+        .add_method(
+            "/posts/:year/:month?",
+            Some(GET.to_string()),
+            handler.clone(),
+        )
+        .unwrap();
+    let (route_methods, params) = route_matcher.match_route("/posts/2024").unwrap();
+    match route_methods.resolve(GET, params.clone()) {
+        RouteMatch::Matched(_, _, route) => assert_eq!(route, "/posts/:year/:month?"),
+        _ => panic!("Expected matched route"),
+    }
+    match route_methods.resolve(POST, params) {
+        RouteMatch::Matched(_, _, route) => assert_eq!(route, "/posts/:year"),
+        _ => panic!("Expected matched route"),
+    }
+}
+
+#[test]
+fn test_route_method_conflicting_params() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    route_matcher
+        .add_method("/users/:id", Some(GET.to_string()), handler.clone())
+        .unwrap();
+    assert!(matches!(
+        route_matcher.add_method("/users/:name", Some(POST.to_string()), handler.clone()),
+        Err(RouteError::ConflictingParams(_))
+    ));
+    route_matcher
+        .add_method("/files/*path", Some(GET.to_string()), handler.clone())
+        .unwrap();
+    assert!(
+        route_matcher
+            .add_method("/files/*rest", Some(PUT.to_string()), handler.clone())
+            .is_err()
+    );
+    let (_, params) = route_matcher.match_route("/users/1").unwrap();
+    assert_eq!(params.get("id"), Some(&"1".to_string()));
+}
+
+#[test]
+fn test_route_matcher_precedence() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    route_matcher.add("/users/:id", handler.clone()).unwrap();
+    route_matcher
+        .add("/users/:id/posts", handler.clone())
+        .unwrap();
+    route_matcher.add("/users/me", handler.clone()).unwrap();
+    route_matcher
+        .add("/users/me/:tab", handler.clone())
+        .unwrap();
+    let (_, params) = route_matcher.match_route("/users/me").unwrap();
+    assert!(params.is_empty());
+    let (_, params) = route_matcher.match_route("/users/1").unwrap();
+    assert_eq!(params.get("id"), Some(&"1".to_string()));
+    let (_, params) = route_matcher.match_route("/users/me/posts").unwrap();
+    assert_eq!(params.get("tab"), Some(&"posts".to_string()));
+    let (_, params) = route_matcher.match_route("/users/you/posts").unwrap();
+    assert_eq!(params.get("id"), Some(&"you".to_string()));
+    assert!(route_matcher.match_route("/users/me/posts/1").is_none());
+    assert!(route_matcher.match_route("/").is_none());
+}
+
+#[test]
+fn test_route_matcher_many_routes() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    let route_count: usize = 5000;
+    for index in 0..route_count {
+        let route: String = format!("/api/v{}/resource{}/:id/detail", index % 10, index);
+        route_matcher.add(&route, handler.clone()).unwrap();
+    }
+    let lookup_count: usize = 20000;
+    for index in 0..lookup_count {
+        let route_index: usize = index % route_count;
+        let path: String = format!(
+            "/api/v{}/resource{}/{}/detail",
+            route_index % 10,
+            route_index,
+            index
+        );
+        let (_, params) = route_matcher.match_route(&path).unwrap();
+        assert_eq!(params.get("id"), Some(&index.to_string()));
+    }
+    assert!(
+        route_matcher
+            .match_route("/api/v0/resource1/1/detail")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_route_matcher_wildcard_and_optional() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    route_matcher.add("/assets/*path", handler.clone()).unwrap();
+    route_matcher
+        .add("/assets/logo.png", handler.clone())
+        .unwrap();
+    route_matcher
+        .add("/posts/:year/:month?", handler.clone())
+        .unwrap();
+    route_matcher.add("/*fallback", handler.clone()).unwrap();
+    assert!(
+        route_matcher
+            .add("/bad/*rest/tail", handler.clone())
+            .is_err()
+    );
+    assert!(route_matcher.add("/posts/:year", handler.clone()).is_err());
+    let (_, params) = route_matcher.match_route("/assets/css/site.css").unwrap();
+    assert_eq!(params.get("path"), Some(&"css/site.css".to_string()));
+    let (_, params) = route_matcher.match_route("/assets/logo.png").unwrap();
+    assert!(params.is_empty());
+    let (_, params) = route_matcher.match_route("/posts/2024").unwrap();
+    assert_eq!(params.get("year"), Some(&"2024".to_string()));
+    assert_eq!(params.get("month"), None);
+    let (_, params) = route_matcher.match_route("/posts/2024/05").unwrap();
+    assert_eq!(params.get("month"), Some(&"05".to_string()));
+    let (_, params) = route_matcher.match_route("/posts/2024/05/01").unwrap();
+    assert_eq!(
+        params.get("fallback"),
+        Some(&"posts/2024/05/01".to_string())
+    );
+    let (_, params) = route_matcher.match_route("/").unwrap();
+    assert_eq!(params.get("fallback"), Some(&"".to_string()));
+}
+
+#[test]
+fn test_route_matcher_constraint() {
+    let handler: ArcFunc = Arc::new(|_: Context| Box::pin(async move {}) as PinBoxFutureSend);
+    let mut route_matcher: RouteMatcher = RouteMatcher::new();
+    route_matcher.add("/user/:name", handler.clone()).unwrap();
+    route_matcher
+        .add(r"/user/:id(\d+)", handler.clone())
+        .unwrap();
+    route_matcher
+        .add("/order/:id<u64>", handler.clone())
+        .unwrap();
+    route_matcher
+        .add(r"/file/:name([a-z]+\.txt)", handler.clone())
+        .unwrap();
+    assert!(
+        route_matcher
+            .add(r"/user/:uid(\d+)", handler.clone())
+            .is_err()
+    );
+    assert!(
+        route_matcher
+            .add("/order/:id<u1>", handler.clone())
+            .is_err()
+    );
+    assert!(route_matcher.add("/order/:id(", handler.clone()).is_err());
+    let (_, params) = route_matcher.match_route("/user/42").unwrap();
+    assert_eq!(params.get("id"), Some(&"42".to_string()));
+    let (_, params) = route_matcher.match_route("/user/alice").unwrap();
+    assert_eq!(params.get("name"), Some(&"alice".to_string()));
+    let (_, params) = route_matcher.match_route("/order/7").unwrap();
+    assert_eq!(params.get("id"), Some(&"7".to_string()));
+    assert!(route_matcher.match_route("/order/-7").is_none());
+    assert!(route_matcher.match_route("/file/notes.txt").is_some());
+    assert!(route_matcher.match_route("/file/Notes.txt").is_none());
+}
+
+#[test]
+fn test_route_pattern_is_match() {
+    let pattern: RoutePattern = RoutePattern::new("/admin/*rest").unwrap();
+    assert!(pattern.is_match("/admin"));
+    assert!(pattern.is_match("/admin/users/1"));
+    assert!(!pattern.is_match("/public"));
+    let pattern: RoutePattern = RoutePattern::new(r"/user/:id(\d+)/:tab?").unwrap();
+    assert!(pattern.is_match("/user/1"));
+    assert!(pattern.is_match("/user/1/posts"));
+    assert!(!pattern.is_match("/user/alice"));
+    assert!(!pattern.is_match("/user/1/posts/2"));
+}
@@ -0,0 +1,6 @@
+pub(crate) const ROUTE_WILDCARD_SYMBOL: &str = "*";
+pub(crate) const ROUTE_OPTIONAL_SYMBOL: &str = "?";
+pub(crate) const ROUTE_REGEX_START_SYMBOL: &str = "(";
+pub(crate) const ROUTE_REGEX_END_SYMBOL: &str = ")";
+pub(crate) const ROUTE_TYPE_START_SYMBOL: &str = "<";
+pub(crate) const ROUTE_TYPE_END_SYMBOL: &str = ">";
@@ -1,5 +1,40 @@
+use crate::*;
+
 #[derive(Debug, Clone)]
 pub(crate) enum RouteSegment {
     Static(String),
-    Dynamic(String),
+    Dynamic(String, OptionRouteConstraint),
+    Optional(String, OptionRouteConstraint),
+    Wildcard(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RouteParamType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    F32,
+    F64,
+    Bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum RouteConstraint {
+    Regex(Regex),
+    Type(RouteParamType),
+}
+
+pub(crate) enum RouteMatch {
+    Matched(ArcFunc, RouteParams, String),
+    MethodNotAllowed(String),
+    NotFound,
 }
@@ -1,12 +1,30 @@
 use crate::*;
 
+impl PartialEq for RouteConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RouteConstraint::Regex(regex1), RouteConstraint::Regex(regex2)) => {
+                regex1.as_str() == regex2.as_str()
+            }
+            (RouteConstraint::Type(type1), RouteConstraint::Type(type2)) => type1 == type2,
+            _ => false,
+        }
+    }
+}
+
 impl PartialEq for RouteSegment {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (RouteSegment::Static(segment1), RouteSegment::Static(segment2)) => {
                 segment1 == segment2
             }
-            (RouteSegment::Dynamic(_), RouteSegment::Dynamic(_)) => true,
+            (RouteSegment::Dynamic(_, constraint1), RouteSegment::Dynamic(_, constraint2)) => {
+                constraint1 == constraint2
+            }
+            (RouteSegment::Optional(_, constraint1), RouteSegment::Optional(_, constraint2)) => {
+                constraint1 == constraint2
+            }
+            (RouteSegment::Wildcard(_), RouteSegment::Wildcard(_)) => true,
             _ => false,
         }
     }
@@ -24,80 +42,396 @@ impl PartialEq for RoutePattern {
     }
 }
 
+impl RouteParamType {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "u8" => Some(Self::U8),
+            "u16" => Some(Self::U16),
+            "u32" => Some(Self::U32),
+            "u64" => Some(Self::U64),
+            "u128" => Some(Self::U128),
+            "usize" => Some(Self::Usize),
+            "i8" => Some(Self::I8),
+            "i16" => Some(Self::I16),
+            "i32" => Some(Self::I32),
+            "i64" => Some(Self::I64),
+            "i128" => Some(Self::I128),
+            "isize" => Some(Self::Isize),
+            "f32" => Some(Self::F32),
+            "f64" => Some(Self::F64),
+            "bool" => Some(Self::Bool),
+            _ => None,
+        }
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            Self::U8 => value.parse::<u8>().is_ok(),
+            Self::U16 => value.parse::<u16>().is_ok(),
+            Self::U32 => value.parse::<u32>().is_ok(),
+            Self::U64 => value.parse::<u64>().is_ok(),
+            Self::U128 => value.parse::<u128>().is_ok(),
+            Self::Usize => value.parse::<usize>().is_ok(),
+            Self::I8 => value.parse::<i8>().is_ok(),
+            Self::I16 => value.parse::<i16>().is_ok(),
+            Self::I32 => value.parse::<i32>().is_ok(),
+            Self::I64 => value.parse::<i64>().is_ok(),
+            Self::I128 => value.parse::<i128>().is_ok(),
+            Self::Isize => value.parse::<isize>().is_ok(),
+            Self::F32 => value.parse::<f32>().is_ok(),
+            Self::F64 => value.parse::<f64>().is_ok(),
+            Self::Bool => value.parse::<bool>().is_ok(),
+        }
+    }
+}
+
+impl RouteConstraint {
+    pub fn parse(param: &str) -> ResultOptionRouteConstraint {
+        if let Some(regex) = param
+            .strip_prefix(ROUTE_REGEX_START_SYMBOL)
+            .and_then(|data| data.strip_suffix(ROUTE_REGEX_END_SYMBOL))
+        {
+            let regex: Regex = Regex::new(&format!("^(?:{})$", regex))
+                .map_err(|err| RouteError::InvalidPattern(err.to_string()))?;
+            return Ok(Some(Self::Regex(regex)));
+        }
+        if let Some(type_name) = param
+            .strip_prefix(ROUTE_TYPE_START_SYMBOL)
+            .and_then(|data| data.strip_suffix(ROUTE_TYPE_END_SYMBOL))
+        {
+            return RouteParamType::parse(type_name)
+                .map(|param_type| Some(Self::Type(param_type)))
+                .ok_or_else(|| RouteError::InvalidPattern(param.to_string()));
+        }
+        if param.is_empty() {
+            return Ok(None);
+        }
+        Err(RouteError::InvalidPattern(param.to_string()))
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(value),
+            Self::Type(param_type) => param_type.is_match(value),
+        }
+    }
+}
+
 impl RoutePattern {
-    pub fn new(route: &str) -> Self {
-        let segments: VecRouteSegment = Self::parse_route(route);
-        Self(segments)
+    pub fn new(route: &str) -> ResultRoutePattern {
+        let segments: VecRouteSegment = Self::parse_route(route)?;
+        let is_wildcard_last: bool = segments
+            .iter()
+            .rev()
+            .skip(1)
+            .all(|segment| !matches!(segment, RouteSegment::Wildcard(_)));
+        if !is_wildcard_last {
+            return Err(RouteError::InvalidPattern(route.to_string()));
+        }
+        Ok(Self(segments))
     }
 
-    fn parse_route(route: &str) -> VecRouteSegment {
+    fn parse_route(route: &str) -> ResultVecRouteSegment {
         let mut segments: VecRouteSegment = Vec::new();
         let route: &str = route.trim_start_matches(DEFAULT_HTTP_PATH);
         if route.is_empty() {
-            return segments;
+            return Ok(segments);
         }
         for segment in route.split(DEFAULT_HTTP_PATH) {
-            if segment.starts_with(COLON_SPACE_SYMBOL) {
-                let param_name: String = segment[1..].to_string();
-                segments.push(RouteSegment::Dynamic(param_name));
-            } else {
-                segments.push(RouteSegment::Static(segment.to_string()));
-            }
+            segments.push(Self::parse_segment(segment)?);
         }
-        segments
+        Ok(segments)
     }
 
-    pub fn match_path(&self, path: &str) -> Option<RouteParams> {
-        let path: &str = path.trim_start_matches(DEFAULT_HTTP_PATH);
-        let path_segments: Vec<&str> = if path.is_empty() {
-            Vec::new()
-        } else {
-            path.split(DEFAULT_HTTP_PATH).collect()
+    fn parse_segment(segment: &str) -> ResultRouteSegment {
+        if let Some(param_name) = segment.strip_prefix(ROUTE_WILDCARD_SYMBOL) {
+            return Ok(RouteSegment::Wildcard(param_name.to_string()));
+        }
+        let param: &str = match segment.strip_prefix(COLON_SPACE_SYMBOL) {
+            Some(param) => param,
+            None => return Ok(RouteSegment::Static(segment.to_string())),
+        };
+        let (param, is_optional): (&str, bool) = match param.strip_suffix(ROUTE_OPTIONAL_SYMBOL) {
+            Some(param) => (param, true),
+            None => (param, false),
         };
-        if path_segments.len() != self.0.len() {
-            return None;
+        let name_end: usize = param
+            .find(|ch: char| {
+                ROUTE_REGEX_START_SYMBOL.starts_with(ch) || ROUTE_TYPE_START_SYMBOL.starts_with(ch)
+            })
+            .unwrap_or(param.len());
+        let param_name: String = param[..name_end].to_string();
+        let constraint: OptionRouteConstraint = RouteConstraint::parse(&param[name_end..])?;
+        if is_optional {
+            return Ok(RouteSegment::Optional(param_name, constraint));
         }
-        let mut params: RouteParams = hash_map_xx_hash3_64();
-        for (idx, segment) in self.0.iter().enumerate() {
+        Ok(RouteSegment::Dynamic(param_name, constraint))
+    }
+
+    pub fn expand(&self) -> VecRoutePattern {
+        let mut patterns: VecRoutePattern = vec![Self(Vec::new())];
+        for segment in &self.0 {
             match segment {
-                RouteSegment::Static(path) => {
-                    if path != path_segments[idx] {
-                        return None;
+                RouteSegment::Optional(param_name, constraint) => {
+                    let mut with_segment: VecRoutePattern = patterns.clone();
+                    for pattern in &mut with_segment {
+                        pattern.0.push(RouteSegment::Dynamic(
+                            param_name.clone(),
+                            constraint.clone(),
+                        ));
                     }
+                    patterns.extend(with_segment);
                 }
-                RouteSegment::Dynamic(param_name) => {
-                    params.insert(param_name.clone(), path_segments[idx].to_string());
+                _ => {
+                    for pattern in &mut patterns {
+                        pattern.0.push(segment.clone());
+                    }
                 }
             }
         }
-        Some(params)
+        patterns
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        let path_segments: Vec<&str> = Self::split_path(path);
+        Self::is_match_segments(&self.0, &path_segments)
+    }
+
+    fn is_match_segments(segments: &[RouteSegment], path_segments: &[&str]) -> bool {
+        if segments.is_empty() {
+            return path_segments.is_empty();
+        }
+        let rest: &[RouteSegment] = &segments[1..];
+        if let RouteSegment::Wildcard(_) = segments[0] {
+            return true;
+        }
+        if let RouteSegment::Optional(_, _) = segments[0] {
+            if Self::is_match_segments(rest, path_segments) {
+                return true;
+            }
+        }
+        if path_segments.is_empty() {
+            return false;
+        }
+        let is_segment_match: bool = match &segments[0] {
+            RouteSegment::Static(segment) => segment == path_segments[0],
+            RouteSegment::Dynamic(_, constraint) | RouteSegment::Optional(_, constraint) => {
+                constraint
+                    .as_ref()
+                    .is_none_or(|constraint| constraint.is_match(path_segments[0]))
+            }
+            RouteSegment::Wildcard(_) => true,
+        };
+        is_segment_match && Self::is_match_segments(rest, &path_segments[1..])
+    }
+
+    pub fn split_path(path: &str) -> Vec<&str> {
+        let path: &str = path.trim_start_matches(DEFAULT_HTTP_PATH);
+        if path.is_empty() {
+            return Vec::new();
+        }
+        path.split(DEFAULT_HTTP_PATH).collect()
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.0
+            .iter()
+            .all(|segment| matches!(segment, RouteSegment::Static(_)))
+    }
+
+    pub fn param_names(&self) -> VecRefString<'_> {
+        self.0
+            .iter()
+            .filter_map(|segment| match segment {
+                RouteSegment::Dynamic(param_name, _)
+                | RouteSegment::Optional(param_name, _)
+                | RouteSegment::Wildcard(param_name) => Some(param_name),
+                RouteSegment::Static(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn params_from_captures(&self, captures: &[Cow<'_, str>]) -> RouteParams {
+        let mut params: RouteParams = hash_map_xx_hash3_64();
+        for (param_name, value) in self.param_names().into_iter().zip(captures.iter()) {
+            params.insert(param_name.clone(), value.to_string());
+        }
+        params
     }
 }
 
-impl RouteMatcher {
+impl RouteMethods {
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self::default()
     }
 
-    pub fn add(&mut self, pattern: &str, handler: ArcFunc) -> ResultAddRoute {
-        let route_pattern: RoutePattern = RoutePattern::new(pattern);
-        let has_same_pattern: bool = self
-            .0
-            .iter()
-            .any(|(tmp_pattern, _)| tmp_pattern == &route_pattern);
-        if has_same_pattern {
-            return Err(RouteError::DuplicatePattern(pattern.to_string()));
+    pub fn insert(&mut self, route: String, method: OptionString, handler: ArcFunc) -> bool {
+        match method {
+            Some(method) => {
+                if self.methods.contains_key(&method) {
+                    return false;
+                }
+                self.methods.insert(method, (route, handler));
+            }
+            None => {
+                if self.any.is_some() {
+                    return false;
+                }
+                self.any = Some((route, handler));
+            }
+        }
+        true
+    }
+
+    pub fn allow(&self) -> String {
+        let mut methods: Vec<&str> = self
+            .methods
+            .keys()
+            .map(|method: &String| method.as_str())
+            .collect();
+        if self.methods.contains_key(GET) && !self.methods.contains_key(HEAD) {
+            methods.push(HEAD);
+        }
+        methods.sort_unstable();
+        methods.join(", ")
+    }
+
+    pub fn resolve(&self, method: &str, params: RouteParams) -> RouteMatch {
+        let route_method_func: Option<&RouteMethodFunc> = match self.methods.get(method) {
+            None if method == HEAD => self.methods.get(GET),
+            route_method_func => route_method_func,
+        };
+        match route_method_func.or(self.any.as_ref()) {
+            Some((route, handler)) => RouteMatch::Matched(handler.clone(), params, route.clone()),
+            None => RouteMatch::MethodNotAllowed(self.allow()),
+        }
+    }
+}
+
+impl RouteNode {
+    pub fn insert(&mut self, segments: &[RouteSegment]) -> &mut Self {
+        let mut node: &mut Self = self;
+        for segment in segments {
+            node = match segment {
+                RouteSegment::Static(path) => node.static_children.entry(path.clone()).or_default(),
+                RouteSegment::Dynamic(_, constraint) | RouteSegment::Optional(_, constraint) => {
+                    node.dynamic_child_mut(constraint)
+                }
+                RouteSegment::Wildcard(_) => {
+                    &mut **node.wildcard_child.get_or_insert_with(Box::default)
+                }
+            };
         }
-        self.0.push((route_pattern, handler));
-        return Ok(());
+        node
     }
 
-    pub fn match_route(&self, path: &str) -> OptionTupleArcFuncRouteParams {
-        for (pattern, handler) in &self.0 {
-            if let Some(params) = pattern.match_path(path) {
-                return Some((handler.clone(), params));
+    fn dynamic_child_mut(&mut self, constraint: &OptionRouteConstraint) -> &mut Self {
+        let position: Option<usize> = self
+            .dynamic_children
+            .iter()
+            .position(|(tmp_constraint, _)| tmp_constraint == constraint);
+        let index: usize = match position {
+            Some(index) => index,
+            None if constraint.is_some() => {
+                let index: usize = self
+                    .dynamic_children
+                    .iter()
+                    .position(|(tmp_constraint, _)| tmp_constraint.is_none())
+                    .unwrap_or(self.dynamic_children.len());
+                self.dynamic_children
+                    .insert(index, (constraint.clone(), RouteNode::default()));
+                index
+            }
+            None => {
+                self.dynamic_children.push((None, RouteNode::default()));
+                self.dynamic_children.len() - 1
+            }
+        };
+        &mut self.dynamic_children[index].1
+    }
+
+    pub fn find<'a, 'b>(
+        &'a self,
+        path_segments: &[&'b str],
+        captures: &mut VecCowStr<'b>,
+    ) -> OptionRefTupleRoutePatternRouteMethods<'a> {
+        if path_segments.is_empty() {
+            if let Some(route) = self.route.as_ref() {
+                return Some(route);
+            }
+        } else {
+            let segment: &str = path_segments[0];
+            let rest: &[&str] = &path_segments[1..];
+            if let Some(child) = self.static_children.get(segment) {
+                if let Some(route) = child.find(rest, captures) {
+                    return Some(route);
+                }
             }
+            for (constraint, child) in &self.dynamic_children {
+                let is_match: bool = constraint
+                    .as_ref()
+                    .is_none_or(|constraint| constraint.is_match(segment));
+                if !is_match {
+                    continue;
+                }
+                captures.push(Cow::Borrowed(path_segments[0]));
+                if let Some(route) = child.find(rest, captures) {
+                    return Some(route);
+                }
+                captures.pop();
+            }
+        }
+        if let Some(route) = self
+            .wildcard_child
+            .as_ref()
+            .and_then(|child| child.route.as_ref())
+        {
+            captures.push(Cow::Owned(path_segments.join(DEFAULT_HTTP_PATH)));
+            return Some(route);
         }
         None
     }
 }
+
+impl RouteMatcher {
+    pub fn new() -> Self {
+        Self(RouteNode::default())
+    }
+
+    pub fn add(&mut self, pattern: &str, handler: ArcFunc) -> ResultAddRoute {
+        self.add_method(pattern, None, handler)
+    }
+
+    pub fn add_method(
+        &mut self,
+        pattern: &str,
+        method: OptionString,
+        handler: ArcFunc,
+    ) -> ResultAddRoute {
+        let route_pattern: RoutePattern = RoutePattern::new(pattern)?;
+        for expanded_pattern in route_pattern.expand() {
+            let node: &mut RouteNode = self.0.insert(&expanded_pattern.0);
+            let (node_pattern, route_methods) = node
+                .route
+                .get_or_insert_with(|| (expanded_pattern.clone(), RouteMethods::new()));
+            if node_pattern.param_names() != expanded_pattern.param_names() {
+                return Err(RouteError::ConflictingParams(pattern.to_string()));
+            }
+            if route_methods.insert(pattern.to_string(), method.clone(), handler.clone()) {
+                continue;
+            }
+            return match method {
+                Some(method) => Err(RouteError::DuplicateMethod(method, pattern.to_string())),
+                None => Err(RouteError::DuplicatePattern(pattern.to_string())),
+            };
+        }
+        Ok(())
+    }
+
+    pub fn match_route(&self, path: &str) -> OptionTupleRouteMethodsRouteParams<'_> {
+        let path_segments: Vec<&str> = RoutePattern::split_path(path);
+        let mut captures: VecCowStr<'_> = Vec::with_capacity(path_segments.len());
+        let (pattern, route_methods) = self.0.find(&path_segments, &mut captures)?;
+        Some((route_methods, pattern.params_from_captures(&captures)))
+    }
+}
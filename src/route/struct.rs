@@ -3,5 +3,19 @@ use crate::*;
 #[derive(Debug, Clone)]
 pub(crate) struct RoutePattern(pub(super) VecRouteSegment);
 
+#[derive(Clone, Default)]
+pub(crate) struct RouteMethods {
+    pub(super) any: OptionRouteMethodFunc,
+    pub(super) methods: HashMapMethodRouteMethodFunc,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct RouteNode {
+    pub(super) static_children: HashMapStringRouteNode,
+    pub(super) dynamic_children: VecRouteDynamicChild,
+    pub(super) wildcard_child: OptionBoxRouteNode,
+    pub(super) route: OptionTupleRoutePatternRouteMethods,
+}
+
 #[derive(Clone)]
-pub(crate) struct RouteMatcher(pub(super) VecRoutePatternArcFunc);
+pub(crate) struct RouteMatcher(pub(super) RouteNode);
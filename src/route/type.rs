@@ -1,12 +1,30 @@
 use crate::*;
 
 pub(crate) type VecRouteSegment = Vec<RouteSegment>;
-pub(crate) type VecRoutePatternArcFunc = Vec<(RoutePattern, ArcFunc)>;
+pub(crate) type VecRoutePattern = Vec<RoutePattern>;
+pub(crate) type ResultRoutePattern = Result<RoutePattern, RouteError>;
+pub(crate) type ResultVecRouteSegment = Result<VecRouteSegment, RouteError>;
+pub(crate) type ResultRouteSegment = Result<RouteSegment, RouteError>;
+pub(crate) type OptionRouteConstraint = Option<RouteConstraint>;
+pub(crate) type ResultOptionRouteConstraint = Result<OptionRouteConstraint, RouteError>;
+pub(crate) type VecRouteDynamicChild = Vec<(OptionRouteConstraint, RouteNode)>;
+pub(crate) type VecCowStr<'a> = Vec<Cow<'a, str>>;
+pub(crate) type VecRefString<'a> = Vec<&'a String>;
+pub(crate) type RouteMethodFunc = (String, ArcFunc);
+pub(crate) type OptionRouteMethodFunc = Option<RouteMethodFunc>;
+pub(crate) type HashMapMethodRouteMethodFunc =
+    HashMap<String, RouteMethodFunc, BuildHasherDefault<XxHash3_64>>;
+pub(crate) type TupleRoutePatternRouteMethods = (RoutePattern, RouteMethods);
+pub(crate) type OptionTupleRoutePatternRouteMethods = Option<TupleRoutePatternRouteMethods>;
+pub(crate) type OptionRefTupleRoutePatternRouteMethods<'a> =
+    Option<&'a TupleRoutePatternRouteMethods>;
+pub(crate) type HashMapStringRouteNode = HashMap<String, RouteNode, BuildHasherDefault<XxHash3_64>>;
+pub(crate) type OptionBoxRouteNode = Option<Box<RouteNode>>;
 pub(crate) type ArcRwLockRouteMatcher = ArcRwLock<RouteMatcher>;
-pub(crate) type HashMapRouteFuncBox = HashMap<String, ArcFunc, BuildHasherDefault<XxHash3_64>>;
-pub(crate) type ArcRwLockHashMapRouteFuncBox = ArcRwLock<HashMapRouteFuncBox>;
-pub(crate) type TupleArcFuncRouteParams = (ArcFunc, RouteParams);
-pub(crate) type OptionTupleArcFuncRouteParams = Option<TupleArcFuncRouteParams>;
+pub(crate) type HashMapRouteMethods = HashMap<String, RouteMethods, BuildHasherDefault<XxHash3_64>>;
+pub(crate) type ArcRwLockHashMapRouteMethods = ArcRwLock<HashMapRouteMethods>;
+pub(crate) type TupleRouteMethodsRouteParams<'a> = (&'a RouteMethods, RouteParams);
+pub(crate) type OptionTupleRouteMethodsRouteParams<'a> = Option<TupleRouteMethodsRouteParams<'a>>;
 pub(crate) type ResultAddRoute = Result<(), RouteError>;
 pub(crate) type RouteParams = HashMap<String, String, BuildHasherDefault<XxHash3_64>>;
 pub(crate) type ArcRwLockRouteParams = ArcRwLock<RouteParams>;
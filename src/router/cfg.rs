@@ -0,0 +1,57 @@
+use crate::*;
+
+#[test]
+fn test_router_join_route() {
+    assert_eq!(Router::join_route("/api/v1", "/users"), "/api/v1/users");
+    assert_eq!(
+        Router::join_route("/api/v1/", "users/:id"),
+        "/api/v1/users/:id"
+    );
+    assert_eq!(Router::join_route("/api/v1", "/"), "/api/v1");
+    assert_eq!(Router::join_route("/", "/"), "/");
+    assert_eq!(Router::join_route("", "/health"), "/health");
+}
+
+#[test]
+fn test_router_is_prefix_match() {
+    assert!(Router::is_prefix_match("/", "/"));
+    assert!(Router::is_prefix_match("/", "/anything"));
+    assert!(Router::is_prefix_match("/api", "/api"));
+    assert!(Router::is_prefix_match("/api/", "/api/users"));
+    assert!(!Router::is_prefix_match("/api", "/apiv2"));
+    assert!(!Router::is_prefix_match("/api", "/"));
+}
+
+#[tokio::test]
+async fn test_router_nest_snapshot() {
+    let router: Router = Router::new();
+    router
+        .request_middleware(|ctx: Context| async move {
+            ctx.set_response_header("x-before", "1").await;
+        })
+        .await;
+    router
+        .get("/users", |ctx: Context| async move {
+            let _ = ctx.send_response(200, "users").await;
+        })
+        .await;
+    let server: Server = Server::new();
+    server.nest("/api", router.clone()).await;
+    router
+        .request_middleware(|ctx: Context| async move {
+            ctx.set_response_header("x-after", "1").await;
+        })
+        .await;
+    router
+        .get("/posts", |ctx: Context| async move {
+            let _ = ctx.send_response(200, "posts").await;
+        })
+        .await;
+    let client: TestClient = server.test_client();
+    let response: TestResponse = client.get("/api/users").send().await;
+    assert_eq!(response.get_body_string(), "users");
+    assert_eq!(response.get_header("x-before"), Some("1".to_string()));
+    assert_eq!(response.get_header("x-after"), None);
+    let response: TestResponse = client.get("/api/posts").send().await;
+    assert_eq!(*response.get_status_code(), 404);
+}
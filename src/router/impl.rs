@@ -0,0 +1,262 @@
+use crate::*;
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            route: arc_rwlock(vec![]),
+            request_middleware: arc_rwlock(vec![]),
+            response_middleware: arc_rwlock(vec![]),
+            middleware: arc_rwlock(vec![]),
+            fallback: arc_rwlock(vec![]),
+        }
+    }
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn join_route(prefix: &str, route: &str) -> String {
+        let prefix: &str = prefix.trim_end_matches(DEFAULT_HTTP_PATH);
+        let route: &str = route.trim_start_matches(DEFAULT_HTTP_PATH);
+        if route.is_empty() {
+            if prefix.is_empty() {
+                return DEFAULT_HTTP_PATH.to_string();
+            }
+            return prefix.to_string();
+        }
+        format!("{}{}{}", prefix, DEFAULT_HTTP_PATH, route)
+    }
+
+    async fn add_route<F, Args>(&self, route: String, method: OptionString, func: F) -> &Self
+    where
+        F: RouteFunc<Args>,
+    {
+        self.get_route()
+            .write()
+            .await
+            .push((route, method, func.into_route_func()));
+        self
+    }
+
+    pub async fn route<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.add_route(route.to_string(), None, func).await
+    }
+
+    pub async fn route_method<M, R, F, Args>(&self, method: M, route: R, func: F) -> &Self
+    where
+        M: ToString,
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        let method: String = method.to_string().to_ascii_uppercase();
+        self.add_route(route.to_string(), Some(method), func).await
+    }
+
+    pub async fn get<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(GET, route, func).await
+    }
+
+    pub async fn post<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(POST, route, func).await
+    }
+
+    pub async fn put<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(PUT, route, func).await
+    }
+
+    pub async fn delete<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(DELETE, route, func).await
+    }
+
+    pub async fn patch<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(PATCH, route, func).await
+    }
+
+    pub async fn options<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(OPTIONS, route, func).await
+    }
+
+    pub async fn head<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(HEAD, route, func).await
+    }
+
+    pub async fn request_middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.get_request_middleware()
+            .write()
+            .await
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    pub async fn response_middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.get_response_middleware()
+            .write()
+            .await
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    pub async fn nest<P>(&self, prefix: P, router: Router) -> &Self
+    where
+        P: ToString,
+    {
+        let prefix: String = prefix.to_string();
+        let routes: VecRouterRoute = router.get_routes(&prefix).await;
+        let mut route_list: RwLockWriteGuard<'_, VecRouterRoute> = self.get_route().write().await;
+        for (route, method, func) in routes {
+            route_list.push((Self::join_route(&prefix, &route), method, func));
+        }
+        let fallbacks: VecRouterFallback = router.get_fallbacks(&prefix).await;
+        let mut fallback_list: RwLockWriteGuard<'_, VecRouterFallback> =
+            self.get_fallback().write().await;
+        for (route, func) in fallbacks {
+            fallback_list.push((Self::join_route(&prefix, &route), func));
+        }
+        self
+    }
+
+    pub async fn fallback<F, Args>(&self, func: F) -> &Self
+    where
+        F: RouteFunc<Args>,
+    {
+        self.get_fallback()
+            .write()
+            .await
+            .push((DEFAULT_HTTP_PATH.to_string(), func.into_route_func()));
+        self
+    }
+
+    pub async fn middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: MiddlewareFuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let arc_func: ArcMiddlewareFunc =
+            Arc::new(move |ctx: Context, next: Next| Box::pin(func(ctx, next)) as PinBoxFutureSend);
+        self.get_middleware().write().await.push(arc_func);
+        self
+    }
+
+    async fn get_middleware_list(&self, prefix: &str) -> ArcVecArcMiddlewareFunc {
+        let request_middleware: VecArcFunc = self.get_request_middleware().read().await.clone();
+        let response_middleware: VecArcFunc = self.get_response_middleware().read().await.clone();
+        let mut middleware_list: VecArcMiddlewareFunc = vec![
+            request_middleware_layer(arc_rwlock(request_middleware)),
+            response_middleware_layer(arc_rwlock(response_middleware)),
+        ];
+        for (index, middleware) in self.get_middleware().read().await.iter().enumerate() {
+            let prefix: String = prefix.to_string();
+            let middleware: ArcMiddlewareFunc = middleware.clone();
+            middleware_list.push(Arc::new(move |ctx: Context, next: Next| {
+                let error_source: ErrorSource =
+                    ErrorSource::RouterMiddleware(prefix.clone(), index);
+                let middleware: ArcMiddlewareFunc = middleware.clone();
+                Box::pin(async move {
+                    ctx.set_error_source(error_source).await;
+                    middleware(ctx, next).await;
+                }) as PinBoxFutureSend
+            }));
+        }
+        Arc::new(middleware_list)
+    }
+
+    fn wrap_middleware(middleware_list: ArcVecArcMiddlewareFunc, func: ArcFunc) -> ArcFunc {
+        Arc::new(move |ctx: Context| {
+            let middleware_list: ArcVecArcMiddlewareFunc = middleware_list.clone();
+            let func: ArcFunc = func.clone();
+            Box::pin(async move {
+                let error_source: ErrorSource = ctx.get_error_source().await;
+                let endpoint: ArcFunc = Arc::new(move |ctx: Context| {
+                    let error_source: ErrorSource = error_source.clone();
+                    let func: ArcFunc = func.clone();
+                    Box::pin(async move {
+                        ctx.set_error_source(error_source).await;
+                        func(ctx).await;
+                    }) as PinBoxFutureSend
+                });
+                Next::new(middleware_list, endpoint).run(ctx).await;
+            }) as PinBoxFutureSend
+        })
+    }
+
+    pub(crate) fn is_prefix_match(prefix: &str, path: &str) -> bool {
+        let prefix: &str = prefix.trim_end_matches(DEFAULT_HTTP_PATH);
+        match path.strip_prefix(prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with(DEFAULT_HTTP_PATH),
+            None => false,
+        }
+    }
+
+    pub(crate) async fn get_fallbacks(&self, prefix: &str) -> VecRouterFallback {
+        let middleware_list: ArcVecArcMiddlewareFunc = self.get_middleware_list(prefix).await;
+        self.get_fallback()
+            .read()
+            .await
+            .iter()
+            .map(|(route, func)| {
+                (
+                    route.clone(),
+                    Self::wrap_middleware(middleware_list.clone(), func.clone()),
+                )
+            })
+            .collect()
+    }
+
+    pub(crate) async fn get_routes(&self, prefix: &str) -> VecRouterRoute {
+        let middleware_list: ArcVecArcMiddlewareFunc = self.get_middleware_list(prefix).await;
+        self.get_route()
+            .read()
+            .await
+            .iter()
+            .map(|(route, method, func)| {
+                (
+                    route.clone(),
+                    method.clone(),
+                    Self::wrap_middleware(middleware_list.clone(), func.clone()),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,8 @@
+pub(crate) mod cfg;
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#type;
+
+pub use r#struct::*;
+
+pub(crate) use r#type::*;
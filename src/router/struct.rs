@@ -0,0 +1,20 @@
+use crate::*;
+
+#[derive(Clone, Getter, Setter)]
+pub struct Router {
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) route: ArcRwLockVecRouterRoute,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) request_middleware: ArcRwLockMiddlewareFuncBox,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) response_middleware: ArcRwLockMiddlewareFuncBox,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) middleware: ArcRwLockVecArcMiddlewareFunc,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) fallback: ArcRwLockVecRouterFallback,
+}
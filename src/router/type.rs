@@ -0,0 +1,9 @@
+use crate::*;
+
+pub(crate) type RouterRoute = (String, OptionString, ArcFunc);
+pub(crate) type VecRouterRoute = Vec<RouterRoute>;
+pub(crate) type ArcRwLockVecRouterRoute = ArcRwLock<VecRouterRoute>;
+pub(crate) type RouterFallback = (String, ArcFunc);
+pub(crate) type OptionRouterFallback = Option<RouterFallback>;
+pub(crate) type VecRouterFallback = Vec<RouterFallback>;
+pub(crate) type ArcRwLockVecRouterFallback = ArcRwLock<VecRouterFallback>;
@@ -0,0 +1,181 @@
+use crate::*;
+
+#[test]
+fn test_parse_request_head() {
+    let mut config: ServerConfig<'_> = ServerConfig::default();
+    config
+        .set_max_uri_length(Some(16))
+        .set_max_header_count(Some(2))
+        .set_max_header_bytes(Some(128))
+        .set_max_body_size(Some(16));
+    let head: &[u8] = b"GET /ok?name=a%20b HTTP/1.1\r\nHost: localhost\r\nContent-Length: 4";
+    let (request, body_kind): ParsedRequestHead = parse_request_head(&config, head).unwrap();
+    assert_eq!(request.get_path(), "/ok");
+    assert_eq!(
+        request.get_querys().get("name").map(String::as_str),
+        Some("a b")
+    );
+    assert_eq!(body_kind, RequestBodyKind::Length(4));
+    let head: &[u8] = b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip, chunked";
+    let (_, body_kind): ParsedRequestHead = parse_request_head(&config, head).unwrap();
+    assert_eq!(body_kind, RequestBodyKind::Chunked);
+    let head: &[u8] = b"GET /too/long/uri/path HTTP/1.1\r\nHost: localhost";
+    assert_eq!(
+        parse_request_head(&config, head).unwrap_err(),
+        RequestReadError::LimitExceeded(414)
+    );
+    let head: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\nA: 1\r\nB: 2";
+    assert_eq!(
+        parse_request_head(&config, head).unwrap_err(),
+        RequestReadError::LimitExceeded(431)
+    );
+    let head: &[u8] = b"POST / HTTP/1.1\r\ncontent-length: 17";
+    assert_eq!(
+        parse_request_head(&config, head).unwrap_err(),
+        RequestReadError::LimitExceeded(413)
+    );
+    for head in [
+        b"NOT A REQUEST".as_slice(),
+        b"GET / HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2",
+        b"POST / HTTP/1.1\r\nContent-Length: 1\r\nTransfer-Encoding: chunked",
+        b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip",
+        b"GET / HTTP/1.1\r\nBad Header: 1",
+    ] {
+        assert!(matches!(
+            parse_request_head(&config, head),
+            Err(RequestReadError::Invalid(_))
+        ));
+    }
+    assert_eq!(
+        find_subslice(b"GET / HTTP/1.1\r\n\r\n", HTTP_HEAD_END),
+        Some(14)
+    );
+}
+
+#[test]
+fn test_parse_chunk_size() {
+    assert_eq!(parse_chunk_size(b"1a"), Ok(26));
+    assert_eq!(parse_chunk_size(b"0;name=value"), Ok(0));
+    assert!(parse_chunk_size(b"").is_err());
+    assert!(parse_chunk_size(b"-1").is_err());
+    assert!(parse_chunk_size(b"ffffffffffffffffff").is_err());
+}
+
+#[test]
+fn test_format_response_head() {
+    let mut headers: ResponseHeaders = hash_map_xx_hash3_64();
+    headers.insert(CONTENT_LENGTH.to_string(), "11".to_string());
+    assert_eq!(
+        format_response_head(206, &headers),
+        b"HTTP/1.1 206 Partial Content\r\ncontent-length: 11\r\n\r\n".to_vec()
+    );
+    assert_eq!(
+        format_response_head(599, &hash_map_xx_hash3_64()),
+        b"HTTP/1.1 599 \r\n\r\n".to_vec()
+    );
+}
+
+#[test]
+fn test_websocket_frame() {
+    let payload: Vec<u8> = vec![7; 300];
+    let frame: Vec<u8> =
+        encode_websocket_frame(WEBSOCKET_OPCODE_BINARY, &payload, Some(TEST_WEBSOCKET_MASK));
+    assert_eq!(frame[1], WEBSOCKET_MASK | 126);
+    assert_ne!(frame[8..], payload[..]);
+    assert_eq!(websocket_frame_size(&frame), Some(frame.len()));
+    assert_eq!(
+        decode_websocket_frame(&frame),
+        Some((
+            (true, WEBSOCKET_OPCODE_BINARY, payload.clone()),
+            frame.len()
+        ))
+    );
+    assert_eq!(decode_websocket_frame(&frame[..frame.len() - 1]), None);
+    let frame: Vec<u8> = encode_websocket_frame(WEBSOCKET_OPCODE_BINARY, &payload, None);
+    assert_eq!(frame[1], 126);
+    assert_eq!(frame[4..], payload[..]);
+    assert_eq!(
+        decode_websocket_frame(b"\x81\x02hi"),
+        Some(((true, WEBSOCKET_OPCODE_TEXT, b"hi".to_vec()), 4))
+    );
+    assert_eq!(websocket_frame_size(b"\x81"), None);
+}
+
+#[test]
+fn test_stream_body_kind() {
+    let mut headers: ResponseHeaders = hash_map_xx_hash3_64();
+    assert_eq!(
+        Server::stream_body_kind(200, &mut headers, StreamResponseKind::Http11),
+        (StreamBodyKind::Chunked, true)
+    );
+    assert_eq!(headers.get(TRANSFER_ENCODING), Some(&CHUNKED.to_string()));
+    let mut headers: ResponseHeaders = hash_map_xx_hash3_64();
+    assert_eq!(
+        Server::stream_body_kind(200, &mut headers, StreamResponseKind::Http10),
+        (StreamBodyKind::Close, false)
+    );
+    headers.insert(CONTENT_LENGTH.to_string(), "3".to_string());
+    headers.insert(CONNECTION.to_string(), CONNECTION_CLOSE.to_string());
+    assert_eq!(
+        Server::stream_body_kind(200, &mut headers, StreamResponseKind::Http11),
+        (StreamBodyKind::Length(3), false)
+    );
+    assert_eq!(
+        Server::stream_body_kind(304, &mut headers, StreamResponseKind::Http11),
+        (StreamBodyKind::Empty, false)
+    );
+    let mut body_kind: StreamBodyKind = StreamBodyKind::Length(3);
+    assert_eq!(
+        Server::encode_stream_body(&mut body_kind, b"abcd".to_vec()),
+        b"abc".to_vec()
+    );
+    assert_eq!(body_kind, StreamBodyKind::Length(0));
+    let mut body_kind: StreamBodyKind = StreamBodyKind::Chunked;
+    assert_eq!(
+        Server::encode_stream_body(&mut body_kind, vec![1; 26]),
+        [b"1a\r\n".to_vec(), vec![1; 26], b"\r\n".to_vec()].concat()
+    );
+}
+
+#[test]
+fn test_accept_error_kind() {
+    use std::io::{Error, ErrorKind};
+
+    let err: Error = Error::from(ErrorKind::ConnectionAborted);
+    assert_eq!(accept_error_kind(&err), AcceptErrorKind::Connection);
+    let err: Error = Error::from(ErrorKind::OutOfMemory);
+    assert_eq!(accept_error_kind(&err), AcceptErrorKind::Resource);
+    #[cfg(unix)]
+    {
+        for errno in [libc::EMFILE, libc::ENFILE, libc::ENOBUFS] {
+            let err: Error = Error::from_raw_os_error(errno);
+            assert_eq!(accept_error_kind(&err), AcceptErrorKind::Resource);
+        }
+    }
+    let err: Error = Error::from(ErrorKind::InvalidInput);
+    assert_eq!(accept_error_kind(&err), AcceptErrorKind::Fatal);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_listener_from_fd() {
+    use std::net::{TcpListener, TcpStream, UdpSocket};
+    use std::os::fd::IntoRawFd;
+
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr: SocketAddr = listener.local_addr().unwrap();
+    let listener: TcpListener = listener_from_fd(listener.into_raw_fd()).unwrap();
+    assert_eq!(listener.local_addr().unwrap(), addr);
+    let stream: TcpStream = TcpStream::connect(addr).unwrap();
+    let stream_fd: RawFd = stream.into_raw_fd();
+    assert!(listener_from_fd(stream_fd).is_err());
+    drop(unsafe { TcpStream::from_raw_fd(stream_fd) });
+    let socket: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let socket_fd: RawFd = socket.into_raw_fd();
+    assert!(listener_from_fd(socket_fd).is_err());
+    drop(unsafe { UdpSocket::from_raw_fd(socket_fd) });
+    let fd_names: Vec<String> = vec!["web".to_string(), String::new()];
+    assert_eq!(listen_fd_name(&fd_names, 0), "web");
+    assert_eq!(listen_fd_name(&fd_names, 1), "fd1");
+    assert_eq!(listen_fd_name(&[], 0), DEFAULT_LISTENER_NAME);
+}
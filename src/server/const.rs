@@ -0,0 +1,41 @@
+#[cfg(feature = "http2")]
+pub(crate) const HTTP2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+pub(crate) const RESPONSE_FRAME_CAPACITY: usize = 16;
+
+pub(crate) const DEFAULT_LISTENER_NAME: &str = "default";
+#[cfg(unix)]
+pub(crate) const LISTEN_PID: &str = "LISTEN_PID";
+#[cfg(unix)]
+pub(crate) const LISTEN_FDS: &str = "LISTEN_FDS";
+#[cfg(unix)]
+pub(crate) const LISTEN_FDNAMES: &str = "LISTEN_FDNAMES";
+#[cfg(unix)]
+pub(crate) const LISTEN_FD_NAME_PREFIX: &str = "fd";
+#[cfg(unix)]
+pub(crate) const LISTEN_FDS_START: RawFd = 3;
+#[cfg(unix)]
+pub(crate) const UNIX_SOCKET_PREFIX: &str = "unix:";
+pub(crate) const HTTP_HEAD_END: &[u8] = b"\r\n\r\n";
+pub(crate) const HTTP_LINE_END: &[u8] = b"\r\n";
+pub(crate) const DEFAULT_MAX_HEADER_BYTES: usize = 64 * 1024;
+pub(crate) const MAX_CHUNK_LINE_SIZE: usize = 4096;
+pub(crate) const ACCEPT_RETRY_MIN_DELAY: Duration = Duration::from_millis(5);
+pub(crate) const ACCEPT_RETRY_MAX_DELAY: Duration = Duration::from_secs(1);
+pub(crate) const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+#[cfg(unix)]
+pub(crate) const ACCEPT_RESOURCE_ERRNOS: &[i32] =
+    &[libc::ENOMEM, libc::ENFILE, libc::EMFILE, libc::ENOBUFS];
+#[cfg(windows)]
+pub(crate) const ACCEPT_RESOURCE_ERRNOS: &[i32] = &[10024, 10055];
+#[cfg(not(any(unix, windows)))]
+pub(crate) const ACCEPT_RESOURCE_ERRNOS: &[i32] = &[];
+pub(crate) const WEBSOCKET_FIN: u8 = 0x80;
+pub(crate) const WEBSOCKET_MASK: u8 = 0x80;
+pub(crate) const WEBSOCKET_OPCODE_TEXT: u8 = 0x1;
+pub(crate) const WEBSOCKET_OPCODE_BINARY: u8 = 0x2;
+pub(crate) const WEBSOCKET_OPCODE_CLOSE: u8 = 0x8;
+pub(crate) const WEBSOCKET_OPCODE_PING: u8 = 0x9;
+pub(crate) const WEBSOCKET_OPCODE_PONG: u8 = 0xA;
+pub(crate) const WEBSOCKET_MAX_FRAME_HEAD_SIZE: usize = 14;
+pub(crate) const WEBSOCKET_CLOSE_MESSAGE_TOO_BIG: u16 = 1009;
+pub(crate) const CHUNKED_BODY_END: &[u8] = b"0\r\n\r\n";
@@ -0,0 +1,63 @@
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ServerState {
+    #[default]
+    Running,
+    ShuttingDown(OptionDuration),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RequestReadError {
+    Closed,
+    TimedOut,
+    LimitExceeded(ResponseStatusCode),
+    Invalid(String),
+    Io(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RequestBodyKind {
+    Length(usize),
+    Chunked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AcceptErrorKind {
+    Connection,
+    Resource,
+    Fatal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StreamResponseKind {
+    Http11,
+    Http10,
+    Head,
+    WebSocket,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StreamBodyKind {
+    Empty,
+    Length(usize),
+    Chunked,
+    Close,
+    WebSocket,
+}
+
+pub(crate) enum ServerListener {
+    Tcp(TcpListener),
+    #[cfg(feature = "tls")]
+    Tls(TcpListener, TlsAcceptor),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+pub(crate) enum ServerConnection {
+    Tcp(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(TcpStream, TlsAcceptor),
+    #[cfg(unix)]
+    Unix(UnixStream, String),
+}
@@ -0,0 +1,455 @@
+use crate::*;
+
+static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+pub(crate) fn next_request_id() -> u64 {
+    REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+#[cfg(unix)]
+static LISTEN_FDS_TAKEN: OnceLock<()> = OnceLock::new();
+
+#[cfg(unix)]
+pub(crate) fn inherited_listeners() -> VecPendingListener {
+    if LISTEN_FDS_TAKEN.set(()).is_err() {
+        return vec![];
+    }
+    let listen_pid: Option<u32> = env::var(LISTEN_PID)
+        .ok()
+        .and_then(|pid: String| pid.parse().ok());
+    let listen_fds: usize = env::var(LISTEN_FDS)
+        .ok()
+        .and_then(|fds: String| fds.parse().ok())
+        .unwrap_or_default();
+    let fd_names: Vec<String> = env::var(LISTEN_FDNAMES)
+        .map(|names: String| names.split(':').map(str::to_string).collect())
+        .unwrap_or_default();
+    if listen_pid != Some(process::id()) {
+        return vec![];
+    }
+    (0..listen_fds)
+        .map(|index: usize| {
+            let fd: RawFd = LISTEN_FDS_START + index as RawFd;
+            (listen_fd_name(&fd_names, index), listener_from_fd(fd))
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+pub(crate) fn listen_fd_name(fd_names: &[String], index: usize) -> String {
+    match fd_names
+        .get(index)
+        .filter(|name: &&String| !name.is_empty())
+    {
+        Some(name) => name.clone(),
+        None if index == 0 => DEFAULT_LISTENER_NAME.to_string(),
+        None => format!("{}{}", LISTEN_FD_NAME_PREFIX, index),
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn listener_from_fd(fd: RawFd) -> IoResult<StdTcpListener> {
+    let socket_type: c_int = socket_option(fd, libc::SO_TYPE)?;
+    let accepting: c_int = socket_option(fd, libc::SO_ACCEPTCONN)?;
+    let family: c_int = socket_family(fd)?;
+    let is_inet: bool = family == libc::AF_INET || family == libc::AF_INET6;
+    if socket_type != libc::SOCK_STREAM || accepting == 0 || !is_inet {
+        return Err(IoError::new(
+            ErrorKind::InvalidInput,
+            format!("fd {} is not a listening TCP socket", fd),
+        ));
+    }
+    // SAFETY: the fd was just checked to be an open, listening TCP socket, and
+    // inherited_listeners hands out each activation fd at most once.
+    Ok(unsafe { StdTcpListener::from_raw_fd(fd) })
+}
+
+#[cfg(unix)]
+fn socket_option(fd: RawFd, option: c_int) -> IoResult<c_int> {
+    let mut value: c_int = 0;
+    let mut len: socklen_t = size_of::<c_int>() as socklen_t;
+    // SAFETY: value and len point to live stack storage sized for an integer
+    // socket option; getsockopt reports a bad fd through its return value.
+    let result: c_int = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            option,
+            &mut value as *mut c_int as *mut c_void,
+            &mut len,
+        )
+    };
+    if result == -1 {
+        return Err(IoError::last_os_error());
+    }
+    Ok(value)
+}
+
+#[cfg(unix)]
+fn socket_family(fd: RawFd) -> IoResult<c_int> {
+    // SAFETY: sockaddr_storage is plain data for which all-zero bytes are valid.
+    let mut addr: sockaddr_storage = unsafe { std::mem::zeroed() };
+    let mut len: socklen_t = size_of::<sockaddr_storage>() as socklen_t;
+    // SAFETY: addr and len point to live stack storage large enough for any
+    // socket address; getsockname reports a bad fd through its return value.
+    let result: c_int = unsafe {
+        libc::getsockname(
+            fd,
+            &mut addr as *mut sockaddr_storage as *mut sockaddr,
+            &mut len,
+        )
+    };
+    if result == -1 {
+        return Err(IoError::last_os_error());
+    }
+    Ok(addr.ss_family as c_int)
+}
+
+#[cfg(unix)]
+pub(crate) fn bind_unix_socket(config: &ServerConfig<'_>, path: &Path) -> IoResult<UnixListener> {
+    remove_stale_unix_socket(path)?;
+    let unix_listener: UnixListener = UnixListener::bind(path)?;
+    if let Some(mode) = *config.get_unix_socket_mode() {
+        set_permissions(path, Permissions::from_mode(mode))?;
+    }
+    let (uid, gid): UnixSocketOwner = *config.get_unix_socket_owner();
+    if uid.is_some() || gid.is_some() {
+        chown(path, uid, gid)?;
+    }
+    Ok(unix_listener)
+}
+
+#[cfg(unix)]
+pub(crate) fn remove_stale_unix_socket(path: &Path) -> IoResult<()> {
+    let metadata: Metadata = match symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(IoError::new(
+            ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        ));
+    }
+    match StdUnixStream::connect(path) {
+        Ok(_) => Err(IoError::new(
+            ErrorKind::AddrInUse,
+            format!("{} is in use", path.display()),
+        )),
+        Err(err) if err.kind() == ErrorKind::ConnectionRefused => remove_file(path),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn unix_peer_description(stream: &UnixStream, path: &Path) -> String {
+    let mut description: String = format!("{}{}", UNIX_SOCKET_PREFIX, path.display());
+    if let Ok(cred) = stream.peer_cred() {
+        if let Some(pid) = cred.pid() {
+            description.push_str(&format!(" pid={}", pid));
+        }
+        description.push_str(&format!(" uid={} gid={}", cred.uid(), cred.gid()));
+    }
+    description
+}
+
+pub(crate) fn find_subslice(data: &[u8], pattern: &[u8]) -> OptionUsize {
+    data.windows(pattern.len())
+        .position(|window: &[u8]| window == pattern)
+}
+
+pub(crate) fn parse_request_head(
+    config: &ServerConfig<'_>,
+    head: &[u8],
+) -> ResultParsedRequestHead {
+    let head: &str = std::str::from_utf8(head)
+        .map_err(|_| RequestReadError::Invalid("Request head is not valid UTF-8".to_string()))?;
+    let mut lines = head.split("\r\n");
+    let request_line: &str = lines.next().unwrap_or_default();
+    let parts: Vec<&str> = request_line.split(' ').collect();
+    let [method, target, version] = parts.as_slice() else {
+        return Err(RequestReadError::Invalid(format!(
+            "Invalid request line: {}",
+            request_line
+        )));
+    };
+    let is_method: bool =
+        !method.is_empty() && method.bytes().all(|byte: u8| byte.is_ascii_uppercase());
+    let is_version: bool = *version == HTTP_VERSION_1_1 || *version == HTTP_VERSION_1_0;
+    if !is_method || !is_version || target.is_empty() {
+        return Err(RequestReadError::Invalid(format!(
+            "Invalid request line: {}",
+            request_line
+        )));
+    }
+    let headers: VecRequestHeaderPair = lines
+        .map(parse_header_line)
+        .collect::<Result<VecRequestHeaderPair, RequestReadError>>()?;
+    let body_kind: RequestBodyKind = request_body_kind(&headers)?;
+    let content_length: OptionUsize = match body_kind {
+        RequestBodyKind::Length(content_length) => Some(content_length),
+        RequestBodyKind::Chunked => None,
+    };
+    config
+        .check_request_limits(target.len(), head.len(), headers.len(), content_length)
+        .map_err(RequestReadError::LimitExceeded)?;
+    let target: &str = target.split('#').next().unwrap_or_default();
+    let (path, query): (&str, &str) = target.split_once('?').unwrap_or((target, ""));
+    let mut request: Request = Request::default();
+    request.set_method(method.parse::<RequestMethod>().unwrap_or_default());
+    request.set_version(version.parse::<RequestVersion>().unwrap_or_default());
+    request.set_path(path);
+    request.set_querys(parse_query(query));
+    for (key, value) in headers {
+        if key == HOST {
+            request.set_host(value.as_str());
+        } else if key == UPGRADE {
+            request.set_upgrade_type(value.parse::<UpgradeType>().unwrap_or_default());
+        }
+        request.set_header(key, value);
+    }
+    Ok((request, body_kind))
+}
+
+pub(crate) fn parse_header_line(line: &str) -> Result<RequestHeaderPair, RequestReadError> {
+    let invalid_header = || RequestReadError::Invalid(format!("Invalid header line: {}", line));
+    let (key, value) = line.split_once(':').ok_or_else(invalid_header)?;
+    if key.is_empty() || key.bytes().any(|byte: u8| byte.is_ascii_whitespace()) {
+        return Err(invalid_header());
+    }
+    Ok((key.to_ascii_lowercase(), value.trim().to_string()))
+}
+
+pub(crate) fn request_body_kind(
+    headers: &[RequestHeaderPair],
+) -> Result<RequestBodyKind, RequestReadError> {
+    let header_values = |name: &'static str| {
+        headers
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let has_content_length: bool = header_values(CONTENT_LENGTH).next().is_some();
+    if let Some(transfer_encoding) = header_values(TRANSFER_ENCODING).last() {
+        let is_chunked: bool = transfer_encoding
+            .rsplit(',')
+            .next()
+            .is_some_and(|coding: &str| coding.trim().eq_ignore_ascii_case(CHUNKED));
+        if !is_chunked || has_content_length {
+            return Err(RequestReadError::Invalid(format!(
+                "Unsupported transfer encoding: {}",
+                transfer_encoding
+            )));
+        }
+        return Ok(RequestBodyKind::Chunked);
+    }
+    let mut content_length: OptionUsize = None;
+    for value in header_values(CONTENT_LENGTH) {
+        let length: OptionUsize = match value.bytes().all(|byte: u8| byte.is_ascii_digit()) {
+            true => value.parse().ok(),
+            false => None,
+        };
+        match length {
+            Some(length) if content_length.is_none_or(|previous: usize| previous == length) => {
+                content_length = Some(length);
+            }
+            _ => {
+                return Err(RequestReadError::Invalid(format!(
+                    "Invalid content length: {}",
+                    value
+                )));
+            }
+        }
+    }
+    Ok(RequestBodyKind::Length(content_length.unwrap_or_default()))
+}
+
+pub(crate) fn parse_chunk_size(line: &[u8]) -> Result<usize, RequestReadError> {
+    let invalid_chunk = || {
+        RequestReadError::Invalid(format!(
+            "Invalid chunk size line: {}",
+            String::from_utf8_lossy(line)
+        ))
+    };
+    let line: &str = std::str::from_utf8(line).map_err(|_| invalid_chunk())?;
+    let size: &str = line.split(';').next().unwrap_or_default().trim();
+    if size.is_empty() || !size.bytes().all(|byte: u8| byte.is_ascii_hexdigit()) {
+        return Err(invalid_chunk());
+    }
+    usize::from_str_radix(size, 16).map_err(|_| invalid_chunk())
+}
+
+pub(crate) fn parse_query(query: &str) -> RequestQuerys {
+    let mut querys: RequestQuerys = hash_map_xx_hash3_64();
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_str(query).unwrap_or_default();
+    querys.extend(pairs);
+    querys
+}
+
+pub(crate) fn status_reason_phrase(status_code: ResponseStatusCode) -> &'static str {
+    match status_code {
+        100 => "Continue",
+        101 => "Switching Protocols",
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        206 => "Partial Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Content Too Large",
+        414 => "URI Too Long",
+        415 => "Unsupported Media Type",
+        416 => "Range Not Satisfiable",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+pub(crate) fn format_response_head(
+    status_code: ResponseStatusCode,
+    headers: &ResponseHeaders,
+) -> Vec<u8> {
+    let mut head: Vec<u8> = format!(
+        "{} {} {}\r\n",
+        HTTP_VERSION_1_1,
+        status_code,
+        status_reason_phrase(status_code)
+    )
+    .into_bytes();
+    for (key, value) in headers.iter() {
+        head.extend_from_slice(format!("{}: {}\r\n", key, value).as_bytes());
+    }
+    head.extend_from_slice(HTTP_LINE_END);
+    head
+}
+
+pub(crate) fn status_has_body(status_code: ResponseStatusCode) -> bool {
+    !(100..200).contains(&status_code) && status_code != 204 && status_code != 304
+}
+
+pub(crate) fn encode_websocket_frame(opcode: u8, payload: &[u8], mask: Option<[u8; 4]>) -> Vec<u8> {
+    let mask_bit: u8 = if mask.is_some() { WEBSOCKET_MASK } else { 0 };
+    let mut frame: Vec<u8> = vec![WEBSOCKET_FIN | opcode];
+    match payload.len() {
+        size if size < 126 => frame.push(mask_bit | size as u8),
+        size if size <= u16::MAX as usize => {
+            frame.push(mask_bit | 126);
+            frame.extend_from_slice(&(size as u16).to_be_bytes());
+        }
+        size => {
+            frame.push(mask_bit | 127);
+            frame.extend_from_slice(&(size as u64).to_be_bytes());
+        }
+    }
+    match mask {
+        Some(mask) => {
+            frame.extend_from_slice(&mask);
+            frame.extend(
+                payload
+                    .iter()
+                    .enumerate()
+                    .map(|(index, byte): (usize, &u8)| byte ^ mask[index % 4]),
+            );
+        }
+        None => frame.extend_from_slice(payload),
+    }
+    frame
+}
+
+pub(crate) fn decode_websocket_frame(buffer: &[u8]) -> OptionWebSocketFrameParts {
+    let first: u8 = *buffer.first()?;
+    let second: u8 = *buffer.get(1)?;
+    let mut offset: usize = 2;
+    let size: usize = match second & !WEBSOCKET_MASK {
+        126 => {
+            let size: [u8; 2] = buffer.get(offset..offset + 2)?.try_into().ok()?;
+            offset += 2;
+            u16::from_be_bytes(size) as usize
+        }
+        127 => {
+            let size: [u8; 8] = buffer.get(offset..offset + 8)?.try_into().ok()?;
+            offset += 8;
+            usize::try_from(u64::from_be_bytes(size)).ok()?
+        }
+        size => size as usize,
+    };
+    let mask: Option<&[u8]> = match second & WEBSOCKET_MASK != 0 {
+        true => {
+            let mask: &[u8] = buffer.get(offset..offset + 4)?;
+            offset += 4;
+            Some(mask)
+        }
+        false => None,
+    };
+    let mut payload: Vec<u8> = buffer.get(offset..offset.checked_add(size)?)?.to_vec();
+    if let Some(mask) = mask {
+        for (index, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[index % 4];
+        }
+    }
+    let frame: WebSocketFrameParts = (first & WEBSOCKET_FIN != 0, first & 0x0F, payload);
+    Some((frame, offset + size))
+}
+
+pub(crate) fn websocket_frame_size(buffer: &[u8]) -> OptionUsize {
+    let second: u8 = *buffer.get(1)?;
+    let mask_size: usize = if second & WEBSOCKET_MASK != 0 { 4 } else { 0 };
+    let (header_size, size): (usize, usize) = match second & !WEBSOCKET_MASK {
+        126 => (
+            4,
+            u16::from_be_bytes(buffer.get(2..4)?.try_into().ok()?) as usize,
+        ),
+        127 => (
+            10,
+            usize::try_from(u64::from_be_bytes(buffer.get(2..10)?.try_into().ok()?)).ok()?,
+        ),
+        size => (2, size as usize),
+    };
+    (header_size + mask_size).checked_add(size)
+}
+
+pub(crate) fn accept_error_kind(err: &IoError) -> AcceptErrorKind {
+    match err.kind() {
+        ErrorKind::ConnectionAborted
+        | ErrorKind::ConnectionReset
+        | ErrorKind::ConnectionRefused
+        | ErrorKind::Interrupted
+        | ErrorKind::WouldBlock
+        | ErrorKind::TimedOut
+        | ErrorKind::PermissionDenied => AcceptErrorKind::Connection,
+        ErrorKind::OutOfMemory => AcceptErrorKind::Resource,
+        _ => match err.raw_os_error() {
+            Some(code) if ACCEPT_RESOURCE_ERRNOS.contains(&code) => AcceptErrorKind::Resource,
+            _ => AcceptErrorKind::Fatal,
+        },
+    }
+}
+
+pub(crate) fn begin_shutdown(state_sender: &WatchSenderServerState, drain_timeout: OptionDuration) {
+    state_sender.send_if_modified(|state: &mut ServerState| {
+        if state.is_shutting_down() {
+            return false;
+        }
+        *state = ServerState::ShuttingDown(drain_timeout);
+        true
+    });
+}
@@ -7,7 +7,13 @@ impl Default for Server {
             route: arc_rwlock(hash_map_xx_hash3_64()),
             route_matcher: arc_rwlock(RouteMatcher::new()),
             request_middleware: arc_rwlock(vec![]),
+            route_middleware: arc_rwlock(vec![]),
             response_middleware: arc_rwlock(vec![]),
+            middleware: arc_rwlock(vec![]),
+            fallback: arc_rwlock(vec![]),
+            listener_route: arc_rwlock(vec![]),
+            pending_listener: arc_rwlock(vec![]),
+            route_concurrency: arc_rwlock(vec![]),
         }
     }
 }
@@ -53,14 +59,29 @@ impl Server {
         self
     }
 
-    pub async fn error_handle<F>(&self, func: F) -> &Self
+    pub async fn error_handle<F, Fut>(&self, func: F) -> &Self
     where
-        F: ErrorHandle + Send + Sync + 'static,
+        F: ErrorHandleWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let error_handle: ArcErrorHandle =
+            Arc::new(move |event: ErrorEvent| Box::pin(func(event)) as PinBoxFutureSend);
+        self.get_config()
+            .write()
+            .await
+            .set_error_handle(error_handle);
+        self
+    }
+
+    pub async fn panic_response<T>(&self, status_code: ResponseStatusCode, body: T) -> &Self
+    where
+        T: Into<ResponseBody>,
     {
         self.get_config()
             .write()
             .await
-            .set_error_handle(Arc::new(func));
+            .set_panic_status_code(status_code)
+            .set_panic_response_body(body.into());
         self
     }
 
@@ -147,230 +168,2536 @@ impl Server {
         self
     }
 
-    pub async fn route<R, F, Fut>(&self, route: R, func: F) -> &Self
+    pub async fn header_read_timeout(&self, header_read_timeout: Duration) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_header_read_timeout(Some(header_read_timeout));
+        self
+    }
+
+    pub async fn body_read_timeout(&self, body_read_timeout: Duration) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_body_read_timeout(Some(body_read_timeout));
+        self
+    }
+
+    pub async fn keep_alive_timeout(&self, keep_alive_timeout: Duration) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_keep_alive_timeout(Some(keep_alive_timeout));
+        self
+    }
+
+    pub async fn handler_timeout(&self, handler_timeout: Duration) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_handler_timeout(Some(handler_timeout));
+        self
+    }
+
+    pub async fn write_timeout(&self, write_timeout: Duration) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_write_timeout(Some(write_timeout));
+        self
+    }
+
+    pub async fn max_body_size(&self, max_body_size: usize) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_max_body_size(Some(max_body_size));
+        self
+    }
+
+    pub async fn max_header_bytes(&self, max_header_bytes: usize) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_max_header_bytes(Some(max_header_bytes));
+        self
+    }
+
+    pub async fn max_header_count(&self, max_header_count: usize) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_max_header_count(Some(max_header_count));
+        self
+    }
+
+    pub async fn max_uri_length(&self, max_uri_length: usize) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_max_uri_length(Some(max_uri_length));
+        self
+    }
+
+    pub async fn max_connections(&self, max_connections: usize) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_max_connections(max_connections);
+        self
+    }
+
+    pub async fn max_in_flight_requests(&self, max_in_flight_requests: usize) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_max_in_flight_requests(max_in_flight_requests);
+        self
+    }
+
+    pub async fn route_concurrency<R>(&self, route: R, max_concurrency: usize) -> &Self
     where
         R: ToString,
-        F: FuncWithoutPin<Fut>,
-        Fut: Future<Output = ()> + Send + 'static,
     {
-        let route_str: String = route.to_string();
-        let arc_func = Arc::new(move |ctx: Context| Box::pin(func(ctx)) as PinBoxFutureSend);
-        self.get_route()
+        let route: String = route.to_string();
+        let route_pattern: RoutePattern = match RoutePattern::new(&route) {
+            Ok(route_pattern) => route_pattern,
+            Err(err) => panic!("{}", err),
+        };
+        self.get_route_concurrency()
             .write()
             .await
-            .insert(route_str.clone(), arc_func.clone());
-        let add_route_matcher_result: ResultAddRoute =
-            self.route_matcher.write().await.add(&route_str, arc_func);
-        if let Err(err) = add_route_matcher_result {
-            panic!("{}", err);
-        }
+            .push((route_pattern, Arc::new(Semaphore::new(max_concurrency))));
         self
     }
 
-    pub async fn request_middleware<F, Fut>(&self, func: F) -> &Self
+    pub async fn listen<N, A>(&self, name: N, addr: A) -> &Self
     where
-        F: FuncWithoutPin<Fut>,
-        Fut: Future<Output = ()> + Send + 'static,
+        N: ToString,
+        A: ToString,
     {
-        self.get_request_middleware()
+        self.get_config()
             .write()
             .await
-            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+            .add_listener(name.to_string(), addr.to_string());
         self
     }
 
-    pub async fn response_middleware<F, Fut>(&self, func: F) -> &Self
+    pub async fn listener<L>(&self, listener: L) -> &Self
     where
-        F: FuncWithoutPin<Fut>,
-        Fut: Future<Output = ()> + Send + 'static,
+        L: IntoStdTcpListener,
     {
-        self.get_response_middleware()
+        self.named_listener(DEFAULT_LISTENER_NAME, listener).await
+    }
+
+    pub async fn named_listener<N, L>(&self, name: N, listener: L) -> &Self
+    where
+        N: ToString,
+        L: IntoStdTcpListener,
+    {
+        self.get_pending_listener()
             .write()
             .await
-            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+            .push((name.to_string(), listener.into_std_tcp_listener()));
         self
     }
 
-    async fn init_panic_hook(&self) {
-        let config: ServerConfig<'_> = self.get_config().read().await.clone();
-        let error_handle: ArcErrorHandle = config.get_error_handle().clone();
-        set_hook(Box::new(move |err: &'_ PanicHookInfo<'_>| {
-            let data: String = err.to_string();
-            error_handle(data);
-        }));
+    #[cfg(unix)]
+    pub async fn listen_fds(&self) -> &Self {
+        self.get_pending_listener()
+            .write()
+            .await
+            .extend(inherited_listeners());
+        self
     }
 
-    async fn init(&self) {
-        self.init_panic_hook().await;
+    #[cfg(unix)]
+    pub async fn unix_socket<P>(&self, path: P) -> &Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.get_config()
+            .write()
+            .await
+            .set_unix_socket(Some(path.into()));
+        self
     }
 
-    pub async fn run(&self) -> ServerResult {
-        self.init().await;
-        let config: ServerConfig<'_> = self.get_config().read().await.clone();
-        let host: &str = *config.get_host();
-        let port: usize = *config.get_port();
-        let nodelay: bool = *config.get_nodelay();
-        let linger: Option<Duration> = *config.get_linger();
-        let ttl_opt: Option<u32> = *config.get_ttl();
-        let http_line_buffer_size: usize = *config.get_http_line_buffer_size();
-        let addr: String = Context::format_host_port(host, &port);
-        let tcp_listener: TcpListener = TcpListener::bind(&addr)
-            .await
-            .map_err(|err| ServerError::TcpBindError(err.to_string()))?;
-        while let Ok((stream, _socket_addr)) = tcp_listener.accept().await {
-            let _ = stream.set_nodelay(nodelay);
-            let _ = stream.set_linger(linger);
-            if let Some(ttl) = ttl_opt {
-                let _ = stream.set_ttl(ttl);
-            }
-            let config_clone: ServerConfig<'_> = config.clone();
-            let stream: ArcRwLockStream = ArcRwLockStream::from_stream(stream);
-            let request_middleware_arc_lock: ArcRwLockMiddlewareFuncBox =
-                self.get_request_middleware().clone();
-            let response_middleware_arc_lock: ArcRwLockMiddlewareFuncBox =
-                self.get_response_middleware().clone();
-            let route_func_arc_lock: ArcRwLockHashMapRouteFuncBox = self.get_route().clone();
-            let route_matcher_arc_lock: ArcRwLockRouteMatcher = self.route_matcher.clone();
-            tokio::spawn(async move {
-                let request_result: RequestReaderHandleResult =
-                    Request::http_request_from_stream(&stream, http_line_buffer_size).await;
-                if request_result.is_err() {
-                    let _ = stream.close().await;
-                    return;
-                }
-                let mut request: Request = request_result.unwrap_or_default();
-                let is_websocket: bool = request.get_upgrade_type().is_websocket();
-                let handler: RequestHandlerImmutableParams = RequestHandlerImmutableParams::new(
-                    &stream,
-                    &config_clone,
-                    &request_middleware_arc_lock,
-                    &response_middleware_arc_lock,
-                    &route_func_arc_lock,
-                    &route_matcher_arc_lock,
-                );
-                match is_websocket {
-                    true => {
-                        Self::handle_websocket_connection(&handler, &mut request).await;
-                    }
-                    false => {
-                        Self::handle_http_connection(&handler, &request).await;
-                    }
-                };
-                let _ = stream.close().await;
-            });
-        }
-        Ok(())
+    #[cfg(unix)]
+    pub async fn unix_socket_permissions(&self, mode: u32) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_unix_socket_mode(Some(mode));
+        self
     }
 
-    async fn handle_request_common<'a>(
-        handler: &RequestHandlerImmutableParams<'a>,
-        request: &Request,
-    ) -> bool {
-        let stream: &ArcRwLockStream = handler.stream;
+    #[cfg(unix)]
+    pub async fn unix_socket_owner(&self, uid: OptionU32, gid: OptionU32) -> &Self {
+        self.get_config()
+            .write()
+            .await
+            .set_unix_socket_owner((uid, gid));
+        self
+    }
 
-        let route: &String = request.get_path();
-        let ctx: Context = Context::from_stream_request(stream, request);
-        for middleware in handler.request_middleware.read().await.iter() {
-            middleware(ctx.clone()).await;
-            if ctx.get_aborted().await {
-                break;
-            }
-        }
-        if !ctx.get_aborted().await {
-            let mut route_handled: bool = false;
-            if let Some(route_handler) = handler.route_func.read().await.get(route) {
-                route_handler(ctx.clone()).await;
-                route_handled = true;
-            }
-            if !route_handled {
-                if let Some((handler_func, params)) =
-                    handler.route_matcher.read().await.match_route(route)
-                {
-                    ctx.set_route_params(params).await;
-                    handler_func(ctx.clone()).await;
-                }
-            }
-            for middleware in handler.response_middleware.read().await.iter() {
-                if ctx.get_aborted().await {
-                    break;
-                }
-                middleware(ctx.clone()).await;
-            }
-        }
-        yield_now().await;
-        request.is_enable_keep_alive()
+    #[cfg(feature = "tls")]
+    async fn add_tls_cert(&self, server_name: OptionString, cert: TlsPemSource, key: TlsPemSource) {
+        self.get_config()
+            .read()
+            .await
+            .get_tls()
+            .add_cert(server_name, cert, key);
     }
 
-    async fn handle_websocket_connection<'a>(
-        handler: &RequestHandlerImmutableParams<'a>,
-        first_request: &mut Request,
-    ) {
-        let stream: &ArcRwLockStream = handler.stream;
-        let buffer_size: usize = *handler.config.get_websocket_buffer_size();
-        let ctx: Context = Context::from_stream_request(stream, first_request);
-        if ctx.handle_websocket().await.is_err() {
-            return;
-        }
-        let route: &String = first_request.get_path();
-        let contains_disable_inner_websocket_handle: bool = handler
-            .config
-            .contains_disable_inner_websocket_handle(route)
+    #[cfg(feature = "tls")]
+    pub async fn tls_pem<C, K>(&self, cert: C, key: K) -> &Self
+    where
+        C: Into<Vec<u8>>,
+        K: Into<Vec<u8>>,
+    {
+        let cert: TlsPemSource = TlsPemSource::Memory(cert.into());
+        let key: TlsPemSource = TlsPemSource::Memory(key.into());
+        self.add_tls_cert(None, cert, key).await;
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn tls_pem_file<C, K>(&self, cert_path: C, key_path: K) -> &Self
+    where
+        C: Into<PathBuf>,
+        K: Into<PathBuf>,
+    {
+        let cert: TlsPemSource = TlsPemSource::File(cert_path.into());
+        let key: TlsPemSource = TlsPemSource::File(key_path.into());
+        self.add_tls_cert(None, cert, key).await;
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn tls_sni_pem<N, C, K>(&self, server_name: N, cert: C, key: K) -> &Self
+    where
+        N: ToString,
+        C: Into<Vec<u8>>,
+        K: Into<Vec<u8>>,
+    {
+        let cert: TlsPemSource = TlsPemSource::Memory(cert.into());
+        let key: TlsPemSource = TlsPemSource::Memory(key.into());
+        self.add_tls_cert(Some(server_name.to_string()), cert, key)
             .await;
-        if contains_disable_inner_websocket_handle {
-            while Self::handle_request_common(handler, first_request).await {}
-            return;
-        }
-        while let Ok(request) = Request::websocket_request_from_stream(stream, buffer_size).await {
-            let body: RequestBody = request.get_body().clone();
-            first_request.set_body(body);
-            let _ = Self::handle_request_common(handler, first_request).await;
-        }
+        self
     }
 
-    async fn handle_http_connection<'a>(
-        handler: &RequestHandlerImmutableParams<'a>,
-        first_request: &Request,
-    ) {
-        let handle_result: bool = Self::handle_request_common(handler, first_request).await;
-        if !handle_result {
-            return;
-        }
-        let stream: ArcRwLockStream = handler.stream.clone();
-        let route: &String = first_request.get_path();
-        let contains_disable_inner_http_handle: bool = handler
-            .config
-            .contains_disable_inner_http_handle(route)
+    #[cfg(feature = "tls")]
+    pub async fn tls_sni_pem_file<N, C, K>(
+        &self,
+        server_name: N,
+        cert_path: C,
+        key_path: K,
+    ) -> &Self
+    where
+        N: ToString,
+        C: Into<PathBuf>,
+        K: Into<PathBuf>,
+    {
+        let cert: TlsPemSource = TlsPemSource::File(cert_path.into());
+        let key: TlsPemSource = TlsPemSource::File(key_path.into());
+        self.add_tls_cert(Some(server_name.to_string()), cert, key)
             .await;
-        let buffer_size: usize = *handler.config.get_http_line_buffer_size();
-        if contains_disable_inner_http_handle {
-            while Self::handle_request_common(handler, first_request).await {}
-            return;
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn tls_client_ca_pem<C>(&self, ca: C) -> &Self
+    where
+        C: Into<Vec<u8>>,
+    {
+        self.get_config()
+            .read()
+            .await
+            .get_tls()
+            .set_client_ca(TlsPemSource::Memory(ca.into()));
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn tls_client_ca_pem_file<P>(&self, path: P) -> &Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.get_config()
+            .read()
+            .await
+            .get_tls()
+            .set_client_ca(TlsPemSource::File(path.into()));
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn tls_client_auth_required(&self, required: bool) -> &Self {
+        self.get_config()
+            .read()
+            .await
+            .get_tls()
+            .set_client_auth_required(required);
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    pub async fn reload_tls(&self) -> ServerResult {
+        let tls: ArcTlsSettings = self.get_config().read().await.get_tls().clone();
+        tls.reload().await
+    }
+
+    pub async fn listener_route<N, R>(&self, name: N, route: R) -> &Self
+    where
+        N: ToString,
+        R: ToString,
+    {
+        let route: String = route.to_string();
+        let route_pattern: RoutePattern = match RoutePattern::new(&route) {
+            Ok(route_pattern) => route_pattern,
+            Err(err) => panic!("{}", err),
+        };
+        self.get_listener_route()
+            .write()
+            .await
+            .push((route_pattern, name.to_string()));
+        self
+    }
+
+    async fn add_route<F, Args>(&self, route: String, method: OptionString, func: F) -> &Self
+    where
+        F: RouteFunc<Args>,
+    {
+        self.add_route_func(route, method, func.into_route_func())
+            .await
+    }
+
+    async fn add_route_func(
+        &self,
+        route: String,
+        method: OptionString,
+        arc_func: ArcFunc,
+    ) -> &Self {
+        let add_route_matcher_result: ResultAddRoute =
+            self.route_matcher
+                .write()
+                .await
+                .add_method(&route, method.clone(), arc_func.clone());
+        if let Err(err) = add_route_matcher_result {
+            panic!("{}", err);
         }
-        while let Ok(request) = Request::http_request_from_stream(&stream, buffer_size).await {
-            let handle_result: bool = Self::handle_request_common(handler, &request).await;
-            if !handle_result {
-                return;
-            }
+        let is_static: bool = RoutePattern::new(&route)
+            .is_ok_and(|route_pattern: RoutePattern| route_pattern.is_static());
+        if !is_static {
+            return self;
         }
+        self.get_route()
+            .write()
+            .await
+            .entry(route.clone())
+            .or_insert_with(RouteMethods::new)
+            .insert(route, method, arc_func);
+        self
     }
-}
 
-impl<'a> RequestHandlerImmutableParams<'a> {
-    pub fn new(
+    pub async fn route<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.add_route(route.to_string(), None, func).await
+    }
+
+    pub async fn route_method<M, R, F, Args>(&self, method: M, route: R, func: F) -> &Self
+    where
+        M: ToString,
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        let method: String = method.to_string().to_ascii_uppercase();
+        self.add_route(route.to_string(), Some(method), func).await
+    }
+
+    pub async fn get<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(GET, route, func).await
+    }
+
+    pub async fn post<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(POST, route, func).await
+    }
+
+    pub async fn put<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(PUT, route, func).await
+    }
+
+    pub async fn delete<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(DELETE, route, func).await
+    }
+
+    pub async fn patch<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(PATCH, route, func).await
+    }
+
+    pub async fn options<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(OPTIONS, route, func).await
+    }
+
+    pub async fn head<R, F, Args>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: RouteFunc<Args>,
+    {
+        self.route_method(HEAD, route, func).await
+    }
+
+    pub async fn static_files<P, R>(&self, prefix: P, root: R) -> &Self
+    where
+        P: ToString,
+        R: Into<PathBuf>,
+    {
+        self.static_files_with(prefix, StaticFiles::new(root)).await
+    }
+
+    pub async fn static_files_with<P>(&self, prefix: P, static_files: StaticFiles) -> &Self
+    where
+        P: ToString,
+    {
+        let prefix: String = prefix.to_string();
+        let route: String = format!(
+            "{}{}{}{}",
+            prefix.trim_end_matches(DEFAULT_HTTP_PATH),
+            DEFAULT_HTTP_PATH,
+            ROUTE_WILDCARD_SYMBOL,
+            STATIC_FILE_PARAM
+        );
+        let static_files: ArcStaticFiles = Arc::new(static_files);
+        let serve = move |ctx: Context| {
+            let static_files: ArcStaticFiles = static_files.clone();
+            async move { static_files.serve(&ctx).await }
+        };
+        self.get(&route, serve.clone()).await;
+        self.head(route, serve).await
+    }
+
+    pub async fn nest<P>(&self, prefix: P, router: Router) -> &Self
+    where
+        P: ToString,
+    {
+        let prefix: String = prefix.to_string();
+        for (route, method, func) in router.get_routes(&prefix).await {
+            self.add_route_func(Router::join_route(&prefix, &route), method, func)
+                .await;
+        }
+        let fallbacks: VecRouterFallback = router.get_fallbacks(&prefix).await;
+        let mut fallback_list: RwLockWriteGuard<'_, VecRouterFallback> =
+            self.get_fallback().write().await;
+        for (route, func) in fallbacks {
+            fallback_list.push((Router::join_route(&prefix, &route), func));
+        }
+        self
+    }
+
+    pub async fn fallback<F, Args>(&self, func: F) -> &Self
+    where
+        F: RouteFunc<Args>,
+    {
+        self.get_fallback()
+            .write()
+            .await
+            .push((DEFAULT_HTTP_PATH.to_string(), func.into_route_func()));
+        self
+    }
+
+    pub async fn request_middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.get_request_middleware()
+            .write()
+            .await
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    pub async fn route_middleware<R, F, Fut>(&self, route: R, func: F) -> &Self
+    where
+        R: ToString,
+        F: FuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let route: String = route.to_string();
+        let route_pattern: RoutePattern = match RoutePattern::new(&route) {
+            Ok(route_pattern) => route_pattern,
+            Err(err) => panic!("{}", err),
+        };
+        let arc_func: ArcFunc =
+            Arc::new(move |ctx: Context| Box::pin(func(ctx)) as PinBoxFutureSend);
+        self.get_route_middleware()
+            .write()
+            .await
+            .push((route_pattern, arc_func));
+        self
+    }
+
+    pub async fn response_middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: FuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.get_response_middleware()
+            .write()
+            .await
+            .push(Arc::new(move |ctx: Context| Box::pin(func(ctx))));
+        self
+    }
+
+    pub async fn middleware<F, Fut>(&self, func: F) -> &Self
+    where
+        F: MiddlewareFuncWithoutPin<Fut>,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut middleware: RwLockWriteGuard<VecArcMiddlewareFunc> =
+            self.get_middleware().write().await;
+        let index: usize = middleware.len();
+        let arc_func: ArcMiddlewareFunc = Arc::new(move |ctx: Context, next: Next| {
+            let middleware_future: Fut = func(ctx.clone(), next);
+            Box::pin(async move {
+                ctx.set_error_source(ErrorSource::Middleware(index)).await;
+                middleware_future.await;
+            }) as PinBoxFutureSend
+        });
+        middleware.push(arc_func);
+        drop(middleware);
+        self
+    }
+
+    async fn create_next(&self) -> Next {
+        let mut middleware: VecArcMiddlewareFunc = vec![
+            request_middleware_layer(self.get_request_middleware().clone()),
+            response_middleware_layer(self.get_response_middleware().clone()),
+        ];
+        middleware.extend(self.get_middleware().read().await.iter().cloned());
+        Next::new(Arc::new(middleware), self.create_endpoint())
+    }
+
+    fn create_endpoint(&self) -> ArcFunc {
+        let route_func: ArcRwLockHashMapRouteMethods = self.get_route().clone();
+        let route_matcher: ArcRwLockRouteMatcher = self.get_route_matcher().clone();
+        let route_middleware: ArcRwLockRouteMiddleware = self.get_route_middleware().clone();
+        let fallback: ArcRwLockVecRouterFallback = self.get_fallback().clone();
+        let listener_route: ArcRwLockVecListenerRoute = self.get_listener_route().clone();
+        let route_concurrency: ArcRwLockVecRouteConcurrency = self.get_route_concurrency().clone();
+        Arc::new(move |ctx: Context| {
+            let route_func: ArcRwLockHashMapRouteMethods = route_func.clone();
+            let route_matcher: ArcRwLockRouteMatcher = route_matcher.clone();
+            let route_middleware: ArcRwLockRouteMiddleware = route_middleware.clone();
+            let fallback: ArcRwLockVecRouterFallback = fallback.clone();
+            let listener_route: ArcRwLockVecListenerRoute = listener_route.clone();
+            let route_concurrency: ArcRwLockVecRouteConcurrency = route_concurrency.clone();
+            Box::pin(async move {
+                let route: RequestPath = ctx.get_request_path().await;
+                let method: String = ctx.get_request_method().await.to_string();
+                let listener: String = ctx.get_listener().await;
+                let route_match: RouteMatch =
+                    match Self::is_listener_allowed(&listener_route, &route, &listener).await {
+                        true => {
+                            Self::match_route(&route_func, &route_matcher, &route, &method).await
+                        }
+                        false => RouteMatch::NotFound,
+                    };
+                match route_match {
+                    RouteMatch::Matched(route_handler, params, route_pattern) => {
+                        let route_permits: OptionVecOwnedSemaphorePermit =
+                            Self::try_acquire_route_concurrency(&route_concurrency, &route).await;
+                        if route_permits.is_none() {
+                            ctx.set_response_status_code(503)
+                                .await
+                                .set_auto_send(true)
+                                .await;
+                            return;
+                        }
+                        ctx.set_route_params(params).await;
+                        for (index, middleware) in
+                            Self::match_route_middleware(&route_middleware, &route).await
+                        {
+                            ctx.set_error_source(ErrorSource::RouteMiddleware(index))
+                                .await;
+                            middleware(ctx.clone()).await;
+                            if ctx.get_aborted().await {
+                                return;
+                            }
+                        }
+                        ctx.set_error_source(ErrorSource::Handler(route_pattern))
+                            .await;
+                        route_handler(ctx.clone()).await;
+                    }
+                    RouteMatch::MethodNotAllowed(allow) => {
+                        ctx.set_response_status_code(405)
+                            .await
+                            .set_response_header(ALLOW, allow)
+                            .await
+                            .set_auto_send(true)
+                            .await;
+                    }
+                    RouteMatch::NotFound => match Self::match_fallback(&fallback, &route).await {
+                        Some((prefix, fallback_handler)) => {
+                            ctx.set_error_source(ErrorSource::Fallback(prefix)).await;
+                            fallback_handler(ctx.clone()).await;
+                        }
+                        None => {
+                            ctx.set_response_status_code(404)
+                                .await
+                                .set_response_body("Not Found")
+                                .await
+                                .set_auto_send(true)
+                                .await;
+                        }
+                    },
+                }
+            }) as PinBoxFutureSend
+        })
+    }
+
+    async fn init(&self) {
+        install_panic_hook();
+    }
+
+    pub async fn start(&self) -> ResultServerHandle {
+        self.init().await;
+        let config: ServerConfig<'static> = self.get_config().read().await.clone();
+        let host: &str = *config.get_host();
+        let port: usize = *config.get_port();
+        let pending_listeners: VecPendingListener =
+            take(&mut *self.get_pending_listener().write().await);
+        let has_default_listener: bool = pending_listeners
+            .iter()
+            .any(|(name, _)| name == DEFAULT_LISTENER_NAME);
+        let mut listener_addrs: VecListenerAddr = vec![];
+        let unix_socket: OptionPathBuf = config.get_unix_socket().clone();
+        if !has_default_listener && unix_socket.is_none() {
+            listener_addrs.push((
+                DEFAULT_LISTENER_NAME.to_string(),
+                Context::format_host_port(host, &port),
+            ));
+        }
+        listener_addrs.extend(config.get_listeners().iter().cloned());
+        let mut tcp_listeners: VecListenerTcpListener = vec![];
+        for (name, listener_result) in pending_listeners {
+            let tcp_listener_result: IoResult<TcpListener> =
+                listener_result.and_then(|listener: StdTcpListener| {
+                    listener.set_nonblocking(true)?;
+                    TcpListener::from_std(listener)
+                });
+            match tcp_listener_result {
+                Ok(tcp_listener) => tcp_listeners.push((name, tcp_listener)),
+                Err(err) => return Err(Self::bind_error(&config, name, err.to_string()).await),
+            }
+        }
+        for (name, addr) in listener_addrs {
+            match TcpListener::bind(&addr).await {
+                Ok(tcp_listener) => tcp_listeners.push((name, tcp_listener)),
+                Err(err) => return Err(Self::bind_error(&config, addr, err.to_string()).await),
+            }
+        }
+        tcp_listeners.sort_by_key(|(name, _)| name != DEFAULT_LISTENER_NAME);
+        let mut local_addrs: VecListenerSocketAddr = vec![];
+        for (name, tcp_listener) in tcp_listeners.iter() {
+            match tcp_listener.local_addr() {
+                Ok(local_addr) => local_addrs.push((name.clone(), local_addr)),
+                Err(err) => {
+                    return Err(Self::bind_error(&config, name.clone(), err.to_string()).await);
+                }
+            }
+        }
+        #[cfg(feature = "tls")]
+        let tls_acceptor: OptionTlsAcceptor = match config.get_tls().is_enabled() {
+            true => Some(config.get_tls().build_acceptor().await?),
+            false => None,
+        };
+        let mut server_listeners: VecListenerServerListener = vec![];
+        #[cfg(unix)]
+        if let Some(path) = &unix_socket {
+            match bind_unix_socket(&config, path) {
+                Ok(unix_listener) => server_listeners.push((
+                    DEFAULT_LISTENER_NAME.to_string(),
+                    ServerListener::Unix(unix_listener, path.clone()),
+                )),
+                Err(err) => {
+                    let addr: String = path.display().to_string();
+                    return Err(Self::bind_error(&config, addr, err.to_string()).await);
+                }
+            }
+        }
+        let (state_sender, _) = watch::channel(ServerState::default());
+        let (drained_sender, drained_receiver) = watch::channel(false);
+        let state_sender: ArcWatchSenderServerState = Arc::new(state_sender);
+        let server: Server = self.clone();
+        let accept_state_sender: ArcWatchSenderServerState = state_sender.clone();
+        let error: ArcRwLockOptionServerError = arc_rwlock(None);
+        let accept_error: ArcRwLockOptionServerError = error.clone();
+        server_listeners.extend(tcp_listeners.into_iter().map(|(name, tcp_listener)| {
+            #[cfg(feature = "tls")]
+            if let Some(tls_acceptor) = &tls_acceptor {
+                return (
+                    name,
+                    ServerListener::Tls(tcp_listener, tls_acceptor.clone()),
+                );
+            }
+            (name, ServerListener::Tcp(tcp_listener))
+        }));
+        spawn(async move {
+            let mut accept_set: JoinSetServerResult = JoinSet::new();
+            for (name, server_listener) in server_listeners {
+                let server: Server = server.clone();
+                let config: ServerConfig<'static> = config.clone();
+                let state_sender: ArcWatchSenderServerState = accept_state_sender.clone();
+                accept_set.spawn(async move {
+                    server
+                        .accept_loop(name, server_listener, config, state_sender)
+                        .await
+                });
+            }
+            while let Some(join_result) = accept_set.join_next().await {
+                if let Ok(Err(err)) = join_result {
+                    let mut accept_error: RwLockWriteGuard<OptionServerError> =
+                        accept_error.write().await;
+                    if accept_error.is_none() {
+                        *accept_error = Some(err);
+                    }
+                }
+            }
+            let _ = drained_sender.send(true);
+        });
+        Ok(ServerHandle {
+            local_addrs,
+            state_sender,
+            drained_receiver,
+            error,
+            unix_socket,
+        })
+    }
+
+    pub async fn oneshot(&self, request: Request) -> Response {
+        let mut path: String = request.get_path().clone();
+        if !request.get_querys().is_empty() {
+            let query: String =
+                serde_urlencoded::to_string(request.get_querys()).unwrap_or_default();
+            path = format!("{}?{}", path, query);
+        }
+        let mut test_request: TestRequest = self
+            .test_client()
+            .request(request.get_method().to_string(), path)
+            .body(request.get_body().clone());
+        for (key, value) in request.get_headers().iter() {
+            test_request = test_request.header(key.as_str(), value.as_str());
+        }
+        let test_response: TestResponse = test_request.send().await;
+        let mut headers: ResponseHeaders = hash_map_xx_hash3_64();
+        headers.extend(test_response.get_headers().iter().cloned());
+        let mut response: Response = Response::default();
+        response
+            .set_status_code(*test_response.get_status_code())
+            .set_headers(headers)
+            .set_body(test_response.get_body().clone());
+        response
+    }
+
+    pub fn test_client(&self) -> TestClient {
+        TestClient::new(self)
+    }
+
+    pub(crate) async fn connect_test_client(&self) -> DuplexStream {
+        self.init().await;
+        let config: ServerConfig<'static> = self.get_config().read().await.clone();
+        let (client_stream, server_stream) = duplex(TEST_CLIENT_BUFFER_SIZE);
+        let connection_permit: OptionOwnedSemaphorePermit =
+            match config.get_connection_semaphore().clone() {
+                Some(semaphore) => semaphore.acquire_owned().await.ok(),
+                None => None,
+            };
+        let next: Next = self.create_next().await;
+        let (state_sender, shutdown) = watch::channel(ServerState::default());
+        spawn(async move {
+            let _connection_permit: OptionOwnedSemaphorePermit = connection_permit;
+            let _state_sender: WatchSenderServerState = state_sender;
+            Self::handle_stream_connection(
+                server_stream,
+                ConnectionInfo::default(),
+                DEFAULT_LISTENER_NAME.to_string(),
+                config,
+                next,
+                shutdown,
+            )
+            .await;
+        });
+        client_stream
+    }
+
+    async fn bind_error(config: &ServerConfig<'_>, addr: String, data: String) -> ServerError {
+        config.get_error_handle()(ErrorEvent::Bind(addr, data.clone())).await;
+        ServerError::TcpBindError(data)
+    }
+
+    pub async fn run(&self) -> ServerResult {
+        self.start().await?.wait().await
+    }
+
+    async fn accept_loop(
+        &self,
+        listener: String,
+        server_listener: ServerListener,
+        config: ServerConfig<'static>,
+        state_sender: ArcWatchSenderServerState,
+    ) -> ServerResult {
+        let mut state_receiver: WatchReceiverServerState = state_sender.subscribe();
+        let nodelay: bool = *config.get_nodelay();
+        let linger: Option<Duration> = *config.get_linger();
+        let ttl_opt: Option<u32> = *config.get_ttl();
+        let connection_semaphore: OptionArcSemaphore = config.get_connection_semaphore().clone();
+        let mut connection_set: JoinSetConnection = JoinSet::new();
+        let mut retry_delay: Duration = ACCEPT_RETRY_MIN_DELAY;
+        let mut loop_result: ServerResult = Ok(());
+        loop {
+            let connection_permit: OptionOwnedSemaphorePermit = match &connection_semaphore {
+                Some(semaphore) => select! {
+                    permit_result = semaphore.clone().acquire_owned() => permit_result.ok(),
+                    _ = state_receiver.wait_for(ServerState::is_shutting_down) => break,
+                },
+                None => None,
+            };
+            select! {
+                accept_result = server_listener.accept() => {
+                    let connection: ServerConnection = match accept_result {
+                        Ok(accepted) => accepted,
+                        Err(err) => {
+                            let error_event: ErrorEvent =
+                                ErrorEvent::Accept(listener.clone(), err.to_string());
+                            config.get_error_handle()(error_event).await;
+                            match accept_error_kind(&err) {
+                                AcceptErrorKind::Connection => continue,
+                                AcceptErrorKind::Resource => {
+                                    select! {
+                                        _ = sleep(retry_delay) => {}
+                                        _ = state_receiver.wait_for(ServerState::is_shutting_down) => break,
+                                    }
+                                    retry_delay = (retry_delay * 2).min(ACCEPT_RETRY_MAX_DELAY);
+                                    continue;
+                                }
+                                AcceptErrorKind::Fatal => {
+                                    begin_shutdown(&state_sender, None);
+                                    loop_result = Err(ServerError::AcceptError(err.to_string()));
+                                    break;
+                                }
+                            }
+                        }
+                    };
+                    retry_delay = ACCEPT_RETRY_MIN_DELAY;
+                    if let Some(stream) = connection.get_tcp_stream() {
+                        let _ = stream.set_nodelay(nodelay);
+                        let _ = stream.set_linger(linger);
+                        if let Some(ttl) = ttl_opt {
+                            let _ = stream.set_ttl(ttl);
+                        }
+                    }
+                    let config_clone: ServerConfig<'static> = config.clone();
+                    let next: Next = self.create_next().await;
+                    let shutdown: WatchReceiverServerState = state_receiver.clone();
+                    let listener: String = listener.clone();
+                    connection_set.spawn(async move {
+                        let _connection_permit: OptionOwnedSemaphorePermit = connection_permit;
+                        match connection {
+                            ServerConnection::Tcp(stream) => {
+                                Self::handle_connection(stream, listener, config_clone, next, shutdown)
+                                    .await;
+                            }
+                            #[cfg(feature = "tls")]
+                            ServerConnection::Tls(stream, tls_acceptor) => {
+                                Self::handle_tls_connection(
+                                    stream,
+                                    tls_acceptor,
+                                    listener,
+                                    config_clone,
+                                    next,
+                                    shutdown,
+                                )
+                                .await;
+                            }
+                            #[cfg(unix)]
+                            ServerConnection::Unix(stream, peer_description) => {
+                                let connection_info: ConnectionInfo = ConnectionInfo {
+                                    peer_description: Some(peer_description),
+                                    ..Default::default()
+                                };
+                                Self::handle_stream_connection(
+                                    stream,
+                                    connection_info,
+                                    listener,
+                                    config_clone,
+                                    next,
+                                    shutdown,
+                                )
+                                .await;
+                            }
+                        }
+                    });
+                }
+                _ = state_receiver.wait_for(ServerState::is_shutting_down) => break,
+                Some(_) = connection_set.join_next(), if !connection_set.is_empty() => {}
+            }
+        }
+        server_listener.close();
+        let drain_timeout: OptionDuration = state_receiver.borrow().get_timeout();
+        Self::drain_connections(&mut connection_set, drain_timeout).await;
+        loop_result
+    }
+
+    async fn drain_connections(
+        connection_set: &mut JoinSetConnection,
+        drain_timeout: OptionDuration,
+    ) {
+        let drain = async { while connection_set.join_next().await.is_some() {} };
+        let drain_timeout: Duration = drain_timeout.unwrap_or(DEFAULT_DRAIN_TIMEOUT);
+        if timeout(drain_timeout, drain).await.is_err() {
+            connection_set.shutdown().await;
+        }
+    }
+
+    async fn until_shutdown<F>(shutdown: &WatchReceiverServerState, future: F) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        let mut shutdown: WatchReceiverServerState = shutdown.clone();
+        select! {
+            biased;
+            _ = shutdown.wait_for(ServerState::is_shutting_down) => None,
+            output = future => Some(output),
+        }
+    }
+
+    #[cfg(feature = "http2")]
+    async fn read_http2_preface(
+        stream: &TcpStream,
+        read_buffer: &mut Vec<u8>,
+    ) -> Result<bool, RequestReadError> {
+        loop {
+            let size: usize = read_buffer.len().min(HTTP2_PREFACE.len());
+            if !HTTP2_PREFACE.starts_with(&read_buffer[..size]) {
+                return Ok(false);
+            }
+            if size == HTTP2_PREFACE.len() {
+                return Ok(true);
+            }
+            stream.readable().await?;
+            read_buffer.reserve(HTTP2_PREFACE.len());
+            match stream.try_read_buf(read_buffer) {
+                Ok(0) => return Err(RequestReadError::Closed),
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    async fn handle_connection(
+        stream: TcpStream,
+        listener: String,
+        config: ServerConfig<'static>,
+        next: Next,
+        shutdown: WatchReceiverServerState,
+    ) {
+        let mut read_buffer: Vec<u8> = vec![];
+        #[cfg(feature = "http2")]
+        {
+            let preface_result: Option<Result<bool, RequestReadError>> = Self::until_shutdown(
+                &shutdown,
+                Self::with_read_timeout(
+                    *config.get_header_read_timeout(),
+                    Self::read_http2_preface(&stream, &mut read_buffer),
+                ),
+            )
+            .await;
+            match preface_result {
+                Some(Ok(true)) => {
+                    let connection_info: ConnectionInfo = ConnectionInfo {
+                        peer_addr: stream.peer_addr().ok(),
+                        ..Default::default()
+                    };
+                    let stream: RewindStream = RewindStream::new(stream, take(&mut read_buffer));
+                    Self::handle_http2_connection(
+                        stream,
+                        connection_info,
+                        listener,
+                        config,
+                        next,
+                        shutdown,
+                    )
+                    .await;
+                    return;
+                }
+                Some(Ok(false)) => {}
+                Some(Err(err)) => {
+                    let stream: ArcRwLockStream = ArcRwLockStream::from_stream(stream);
+                    Self::handle_read_error(&stream, &config, &listener, err).await;
+                    let _ = stream.close().await;
+                    return;
+                }
+                None => return,
+            }
+        }
+        let stream: ArcRwLockStream = ArcRwLockStream::from_stream(stream);
+        let request_result: Option<ResultRequestRead> = Self::read_tcp_request_until_shutdown(
+            &stream,
+            &config,
+            &mut read_buffer,
+            None,
+            &shutdown,
+        )
+        .await;
+        let mut request: Request = match request_result {
+            Some(Ok(request)) => request,
+            Some(Err(err)) => {
+                Self::handle_read_error(&stream, &config, &listener, err).await;
+                let _ = stream.close().await;
+                return;
+            }
+            None => {
+                let _ = stream.close().await;
+                return;
+            }
+        };
+        let is_websocket: bool = request.get_upgrade_type().is_websocket();
+        let handler: RequestHandlerImmutableParams =
+            RequestHandlerImmutableParams::new(&stream, &config, &next, &shutdown, &listener);
+        match is_websocket {
+            true => {
+                Self::handle_websocket_connection(&handler, &mut request).await;
+            }
+            false => {
+                Self::handle_http_connection(&handler, &request, &mut read_buffer).await;
+            }
+        };
+        let _ = stream.close().await;
+    }
+
+    #[cfg(feature = "tls")]
+    async fn handle_tls_connection(
+        stream: TcpStream,
+        tls_acceptor: TlsAcceptor,
+        listener: String,
+        config: ServerConfig<'static>,
+        next: Next,
+        shutdown: WatchReceiverServerState,
+    ) {
+        let peer: OptionSocketAddr = stream.peer_addr().ok();
+        let handshake_result: Option<Result<ServerTlsStream<TcpStream>, RequestReadError>> =
+            Self::until_shutdown(
+                &shutdown,
+                Self::with_read_timeout(*config.get_header_read_timeout(), async {
+                    tls_acceptor
+                        .accept(stream)
+                        .await
+                        .map_err(RequestReadError::from)
+                }),
+            )
+            .await;
+        let stream: ServerTlsStream<TcpStream> = match handshake_result {
+            Some(Ok(stream)) => stream,
+            Some(Err(err)) => {
+                let data: String = match err {
+                    RequestReadError::TimedOut => TLS_HANDSHAKE_TIMED_OUT.to_string(),
+                    RequestReadError::Invalid(data) | RequestReadError::Io(data) => data,
+                    RequestReadError::Closed | RequestReadError::LimitExceeded(_) => return,
+                };
+                config.get_error_handle()(ErrorEvent::Read(listener, peer, data)).await;
+                return;
+            }
+            None => return,
+        };
+        let (_, tls_connection) = stream.get_ref();
+        let connection_info: ConnectionInfo = ConnectionInfo {
+            peer_addr: peer,
+            peer_description: None,
+            tls_alpn_protocol: tls_connection
+                .alpn_protocol()
+                .map(|protocol: &[u8]| String::from_utf8_lossy(protocol).into_owned()),
+            tls_peer_certificates: tls_connection
+                .peer_certificates()
+                .map(|certs: &[CertificateDer<'static>]| {
+                    certs.iter().map(|cert| cert.to_vec()).collect()
+                })
+                .unwrap_or_default(),
+        };
+        #[cfg(feature = "http2")]
+        if connection_info.tls_alpn_protocol.as_deref() == Some(TLS_ALPN_H2) {
+            Self::handle_http2_connection(
+                stream,
+                connection_info,
+                listener,
+                config,
+                next,
+                shutdown,
+            )
+            .await;
+            return;
+        }
+        Self::handle_stream_connection(stream, connection_info, listener, config, next, shutdown)
+            .await;
+    }
+
+    #[cfg(feature = "http2")]
+    async fn handle_http2_connection<S>(
+        stream: S,
+        connection_info: ConnectionInfo,
+        listener: String,
+        config: ServerConfig<'static>,
+        next: Next,
+        shutdown: WatchReceiverServerState,
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin + Send,
+    {
+        let peer: OptionSocketAddr = connection_info.peer_addr;
+        let mut builder: Http2Builder = Http2Builder::new();
+        if let Some(max_header_bytes) = *config.get_max_header_bytes() {
+            builder.max_header_list_size(u32::try_from(max_header_bytes).unwrap_or(u32::MAX));
+        }
+        let mut connection = match builder.handshake(stream).await {
+            Ok(connection) => connection,
+            Err(err) => {
+                let error_event: ErrorEvent = ErrorEvent::Read(listener, peer, err.to_string());
+                config.get_error_handle()(error_event).await;
+                return;
+            }
+        };
+        let mut stream_set: JoinSetConnection = JoinSet::new();
+        while let Some(Some(accept_result)) =
+            Self::until_shutdown(&shutdown, connection.accept()).await
+        {
+            let (request, respond) = match accept_result {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    let error_event: ErrorEvent =
+                        ErrorEvent::Read(listener.clone(), peer, err.to_string());
+                    config.get_error_handle()(error_event).await;
+                    break;
+                }
+            };
+            stream_set.spawn(Self::handle_http2_stream(
+                request,
+                respond,
+                connection_info.clone(),
+                listener.clone(),
+                config.clone(),
+                next.clone(),
+            ));
+        }
+        if shutdown.borrow().is_shutting_down() {
+            connection.graceful_shutdown();
+            while let Some(accept_result) = connection.accept().await {
+                if let Ok((request, respond)) = accept_result {
+                    stream_set.spawn(Self::handle_http2_stream(
+                        request,
+                        respond,
+                        connection_info.clone(),
+                        listener.clone(),
+                        config.clone(),
+                        next.clone(),
+                    ));
+                }
+            }
+        }
+        while stream_set.join_next().await.is_some() {}
+    }
+
+    #[cfg(feature = "http2")]
+    async fn handle_http2_stream(
+        request: Http2Request<RecvStream>,
+        mut respond: SendResponse<Bytes>,
+        connection_info: ConnectionInfo,
+        listener: String,
+        config: ServerConfig<'static>,
+        next: Next,
+    ) {
+        let (parts, mut body) = request.into_parts();
+        let uri_length: usize = parts
+            .uri
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str().len())
+            .unwrap_or_default();
+        let header_bytes: usize = parts
+            .headers
+            .iter()
+            .map(|(key, value)| key.as_str().len() + value.len())
+            .sum();
+        let content_length: OptionUsize = parts
+            .headers
+            .get(CONTENT_LENGTH.to_ascii_lowercase())
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value: &str| value.parse().ok());
+        if let Err(status_code) = config.check_request_limits(
+            uri_length,
+            header_bytes,
+            parts.headers.len(),
+            content_length,
+        ) {
+            Self::send_http2_status(&mut respond, status_code);
+            return;
+        }
+        let Ok(_in_flight_permit) = config.try_acquire_in_flight() else {
+            Self::send_http2_status(&mut respond, 503);
+            return;
+        };
+        let max_body_size: OptionUsize = *config.get_max_body_size();
+        let mut request_body: Vec<u8> = vec![];
+        while let Some(chunk_result) = body.data().await {
+            let Ok(chunk) = chunk_result else {
+                return;
+            };
+            let _ = body.flow_control().release_capacity(chunk.len());
+            request_body.extend_from_slice(&chunk);
+            if max_body_size.is_some_and(|max: usize| request_body.len() > max) {
+                Self::send_http2_status(&mut respond, 413);
+                return;
+            }
+        }
+        let (response_sender, response_receiver) = mpsc::channel(RESPONSE_FRAME_CAPACITY);
+        let ctx: Context = Context::detached();
+        ctx.set_request_id(next_request_id())
+            .await
+            .set_listener(listener)
+            .await
+            .set_write_timeout(*config.get_write_timeout())
+            .await
+            .set_response_sender(Some(response_sender))
+            .await
+            .set_request_path(parts.uri.path())
+            .await
+            .set_request_querys(parse_query(parts.uri.query().unwrap_or_default()))
+            .await
+            .set_request_body(request_body)
+            .await;
+        Self::set_connection_info(&ctx, &connection_info).await;
+        if let Ok(method) = parts.method.as_str().parse::<RequestMethod>() {
+            ctx.set_request_method(method).await;
+        }
+        if let Some(authority) = parts.uri.authority() {
+            ctx.set_request_host(authority.as_str()).await;
+        }
+        for (key, value) in parts.headers.iter() {
+            if let Ok(value) = value.to_str() {
+                ctx.set_request_header(key.as_str(), value).await;
+            }
+        }
+        let handler_future = async {
+            if Self::run_handler(&ctx, &next, &config).await {
+                Self::send_if_auto_send(&ctx, &config).await;
+            }
+            if !ctx.get_responded().await {
+                let _ = ctx.send().await;
+            }
+            ctx.set_response_sender(None).await;
+        };
+        join!(
+            handler_future,
+            Self::forward_http2_response(response_receiver, respond)
+        );
+    }
+
+    #[cfg(feature = "http2")]
+    async fn forward_http2_response(
+        mut response_receiver: ResponseFrameReceiver,
+        mut respond: SendResponse<Bytes>,
+    ) {
+        let mut send_stream: OptionHttp2SendStream = None;
+        while let Some(response_frame) = response_receiver.recv().await {
+            match response_frame {
+                ResponseFrame::Head(status_code, headers) => {
+                    if send_stream.is_some() {
+                        continue;
+                    }
+                    let Some(response) = Self::http2_response(status_code, &headers) else {
+                        Self::send_http2_status(&mut respond, 500);
+                        return;
+                    };
+                    match respond.send_response(response, false) {
+                        Ok(stream) => send_stream = Some(stream),
+                        Err(_) => return,
+                    }
+                }
+                ResponseFrame::Body(body) => {
+                    let Some(stream) = send_stream.as_mut() else {
+                        continue;
+                    };
+                    if !Self::send_http2_data(stream, Bytes::from(body)).await {
+                        return;
+                    }
+                }
+                ResponseFrame::End => break,
+            }
+        }
+        match send_stream {
+            Some(mut stream) => {
+                let _ = stream.send_data(Bytes::new(), true);
+            }
+            None => Self::send_http2_status(&mut respond, 500),
+        }
+    }
+
+    #[cfg(feature = "http2")]
+    fn http2_response(
+        status_code: ResponseStatusCode,
+        headers: &ResponseHeaders,
+    ) -> Option<Http2Response<()>> {
+        let mut response_builder = Http2Response::builder().status(status_code as u16);
+        for (key, value) in headers.iter() {
+            if Self::is_http2_connection_header(key) {
+                continue;
+            }
+            response_builder = response_builder.header(key.to_ascii_lowercase(), value.as_str());
+        }
+        response_builder.body(()).ok()
+    }
+
+    #[cfg(feature = "http2")]
+    async fn send_http2_data(send_stream: &mut SendStream<Bytes>, mut data: Bytes) -> bool {
+        while !data.is_empty() {
+            send_stream.reserve_capacity(data.len());
+            let capacity: usize =
+                match poll_fn(|cx: &mut TaskContext<'_>| send_stream.poll_capacity(cx)).await {
+                    Some(Ok(capacity)) => capacity,
+                    _ => return false,
+                };
+            let chunk: Bytes = data.split_to(capacity.min(data.len()));
+            if send_stream.send_data(chunk, false).is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[cfg(feature = "http2")]
+    fn send_http2_status(respond: &mut SendResponse<Bytes>, status_code: ResponseStatusCode) {
+        let response: Http2Response<()> = Http2Response::builder()
+            .status(status_code as u16)
+            .body(())
+            .unwrap_or_default();
+        let _ = respond.send_response(response, true);
+    }
+
+    #[cfg(feature = "http2")]
+    fn is_http2_connection_header(key: &str) -> bool {
+        [
+            CONNECTION,
+            "keep-alive",
+            "proxy-connection",
+            "transfer-encoding",
+            "upgrade",
+        ]
+        .iter()
+        .any(|header: &&str| header.eq_ignore_ascii_case(key))
+    }
+
+    async fn read_tcp_request_until_shutdown(
+        stream: &ArcRwLockStream,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+        idle_timeout: OptionDuration,
+        shutdown: &WatchReceiverServerState,
+    ) -> Option<ResultRequestRead> {
+        let read_stream: RwLockReadGuard<'_, TcpStream> = stream.get_read_lock().await;
+        let mut reader: TcpStreamReader<'_> = TcpStreamReader(&read_stream);
+        Self::read_http_request_until_shutdown(
+            &mut reader,
+            config,
+            read_buffer,
+            idle_timeout,
+            shutdown,
+        )
+        .await
+    }
+
+    async fn read_http_request_until_shutdown<R>(
+        reader: &mut R,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+        idle_timeout: OptionDuration,
+        shutdown: &WatchReceiverServerState,
+    ) -> Option<ResultRequestRead>
+    where
+        R: AsyncRead + Unpin,
+    {
+        if read_buffer.is_empty() {
+            let start_result: Result<(), RequestReadError> = Self::until_shutdown(
+                shutdown,
+                Self::read_request_start(reader, config, read_buffer, idle_timeout),
+            )
+            .await?;
+            if let Err(err) = start_result {
+                return Some(Err(err));
+            }
+        }
+        Some(Self::read_http_request(reader, config, read_buffer).await)
+    }
+
+    async fn read_request_start<R>(
+        reader: &mut R,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+        idle_timeout: OptionDuration,
+    ) -> Result<(), RequestReadError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let buffer_size: usize = *config.get_http_line_buffer_size();
+        let fill = Self::fill_read_buffer(reader, read_buffer, buffer_size);
+        match idle_timeout {
+            Some(idle_timeout) => timeout(idle_timeout, fill)
+                .await
+                .map_err(|_| RequestReadError::Closed)?,
+            None => Self::with_read_timeout(*config.get_header_read_timeout(), fill).await,
+        }
+    }
+
+    async fn read_http_request<R>(
+        reader: &mut R,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+    ) -> ResultRequestRead
+    where
+        R: AsyncRead + Unpin,
+    {
+        let head_future = async {
+            let head_end: usize = Self::read_request_head(reader, config, read_buffer).await?;
+            let parsed_head: ParsedRequestHead =
+                parse_request_head(config, &read_buffer[..head_end])?;
+            read_buffer.drain(..head_end + HTTP_HEAD_END.len());
+            Ok(parsed_head)
+        };
+        let (mut request, body_kind): ParsedRequestHead =
+            Self::with_read_timeout(*config.get_header_read_timeout(), head_future).await?;
+        let body: RequestBody = Self::with_read_timeout(
+            *config.get_body_read_timeout(),
+            Self::read_request_body(reader, config, read_buffer, body_kind),
+        )
+        .await?;
+        request.set_body(body);
+        Ok(request)
+    }
+
+    async fn with_read_timeout<F, T>(
+        read_timeout: OptionDuration,
+        future: F,
+    ) -> Result<T, RequestReadError>
+    where
+        F: Future<Output = Result<T, RequestReadError>>,
+    {
+        match read_timeout {
+            Some(read_timeout) => timeout(read_timeout, future)
+                .await
+                .map_err(|_| RequestReadError::TimedOut)?,
+            None => future.await,
+        }
+    }
+
+    async fn fill_read_buffer<R>(
+        reader: &mut R,
+        read_buffer: &mut Vec<u8>,
+        buffer_size: usize,
+    ) -> Result<(), RequestReadError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        read_buffer.reserve(buffer_size);
+        match reader.read_buf(read_buffer).await? {
+            0 => Err(RequestReadError::Closed),
+            _ => Ok(()),
+        }
+    }
+
+    async fn read_request_head<R>(
+        reader: &mut R,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+    ) -> Result<usize, RequestReadError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let buffer_size: usize = *config.get_http_line_buffer_size();
+        let max_header_bytes: usize = config
+            .get_max_header_bytes()
+            .unwrap_or(DEFAULT_MAX_HEADER_BYTES);
+        let mut searched: usize = 0;
+        loop {
+            if let Some(head_end) = find_subslice(&read_buffer[searched..], HTTP_HEAD_END) {
+                return Ok(searched + head_end);
+            }
+            if read_buffer.len() > max_header_bytes + HTTP_HEAD_END.len() {
+                let has_request_line: bool = find_subslice(read_buffer, HTTP_LINE_END).is_some();
+                if !has_request_line && config.get_max_uri_length().is_some() {
+                    return Err(RequestReadError::LimitExceeded(414));
+                }
+                return Err(RequestReadError::LimitExceeded(431));
+            }
+            searched = read_buffer.len().saturating_sub(HTTP_HEAD_END.len() - 1);
+            Self::fill_read_buffer(reader, read_buffer, buffer_size).await?;
+        }
+    }
+
+    async fn read_request_body<R>(
+        reader: &mut R,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+        body_kind: RequestBodyKind,
+    ) -> ResultRequestBody
+    where
+        R: AsyncRead + Unpin,
+    {
+        let buffer_size: usize = *config.get_http_line_buffer_size();
+        let content_length: usize = match body_kind {
+            RequestBodyKind::Length(content_length) => content_length,
+            RequestBodyKind::Chunked => {
+                return Self::read_chunked_body(reader, config, read_buffer).await;
+            }
+        };
+        while read_buffer.len() < content_length {
+            Self::fill_read_buffer(reader, read_buffer, buffer_size).await?;
+        }
+        Ok(read_buffer.drain(..content_length).collect())
+    }
+
+    async fn read_chunked_body<R>(
+        reader: &mut R,
+        config: &ServerConfig<'_>,
+        read_buffer: &mut Vec<u8>,
+    ) -> ResultRequestBody
+    where
+        R: AsyncRead + Unpin,
+    {
+        let buffer_size: usize = *config.get_http_line_buffer_size();
+        let max_body_size: OptionUsize = *config.get_max_body_size();
+        let mut body: RequestBody = vec![];
+        loop {
+            let chunk_line: Vec<u8> =
+                Self::read_chunk_line(reader, read_buffer, buffer_size).await?;
+            let chunk_size: usize = parse_chunk_size(&chunk_line)?;
+            if chunk_size == 0 {
+                break;
+            }
+            let body_size: usize = body.len().saturating_add(chunk_size);
+            if max_body_size.is_some_and(|max: usize| body_size > max) {
+                return Err(RequestReadError::LimitExceeded(413));
+            }
+            let chunk_end: usize = chunk_size
+                .checked_add(HTTP_LINE_END.len())
+                .ok_or_else(|| RequestReadError::Invalid("Chunk size overflow".to_string()))?;
+            while read_buffer.len() < chunk_end {
+                Self::fill_read_buffer(reader, read_buffer, buffer_size).await?;
+            }
+            if &read_buffer[chunk_size..chunk_end] != HTTP_LINE_END {
+                return Err(RequestReadError::Invalid(
+                    "Chunk data is not followed by CRLF".to_string(),
+                ));
+            }
+            body.extend_from_slice(&read_buffer[..chunk_size]);
+            read_buffer.drain(..chunk_end);
+        }
+        let max_trailer_bytes: usize = config
+            .get_max_header_bytes()
+            .unwrap_or(DEFAULT_MAX_HEADER_BYTES);
+        let mut trailer_bytes: usize = 0;
+        loop {
+            let trailer_line: Vec<u8> =
+                Self::read_chunk_line(reader, read_buffer, buffer_size).await?;
+            if trailer_line.is_empty() {
+                return Ok(body);
+            }
+            trailer_bytes += trailer_line.len() + HTTP_LINE_END.len();
+            if trailer_bytes > max_trailer_bytes {
+                return Err(RequestReadError::LimitExceeded(431));
+            }
+        }
+    }
+
+    async fn read_chunk_line<R>(
+        reader: &mut R,
+        read_buffer: &mut Vec<u8>,
+        buffer_size: usize,
+    ) -> Result<Vec<u8>, RequestReadError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let max_line_size: usize = MAX_CHUNK_LINE_SIZE + HTTP_LINE_END.len();
+        loop {
+            let window: usize = read_buffer.len().min(max_line_size);
+            if let Some(line_end) = find_subslice(&read_buffer[..window], HTTP_LINE_END) {
+                let line: Vec<u8> = read_buffer[..line_end].to_vec();
+                read_buffer.drain(..line_end + HTTP_LINE_END.len());
+                return Ok(line);
+            }
+            if window == max_line_size {
+                return Err(RequestReadError::Invalid(
+                    "Chunk line is too long".to_string(),
+                ));
+            }
+            Self::fill_read_buffer(reader, read_buffer, buffer_size).await?;
+        }
+    }
+
+    async fn handle_read_error(
+        stream: &ArcRwLockStream,
+        config: &ServerConfig<'_>,
+        listener: &str,
+        err: RequestReadError,
+    ) {
+        let ctx: Context = Context::from_stream_request(stream, &Request::default());
+        Self::handle_context_read_error(&ctx, config, listener, err).await;
+    }
+
+    async fn handle_context_read_error(
+        ctx: &Context,
+        config: &ServerConfig<'_>,
+        listener: &str,
+        err: RequestReadError,
+    ) {
+        let (status_code, data): (Option<ResponseStatusCode>, String) = match err {
+            RequestReadError::Closed => return,
+            RequestReadError::TimedOut => (Some(408), "Request read timed out".to_string()),
+            RequestReadError::LimitExceeded(status_code) => (
+                Some(status_code),
+                format!("Request rejected with status {}", status_code),
+            ),
+            RequestReadError::Invalid(data) => (Some(400), data),
+            RequestReadError::Io(data) => (None, data),
+        };
+        let event: ErrorEvent =
+            ErrorEvent::Read(listener.to_string(), ctx.get_socket_addr().await, data);
+        config.get_error_handle()(event).await;
+        if let Some(status_code) = status_code {
+            Self::send_read_error(ctx, config, status_code).await;
+        }
+    }
+
+    async fn send_read_error(
+        ctx: &Context,
+        config: &ServerConfig<'_>,
+        status_code: ResponseStatusCode,
+    ) {
+        let _ = ctx
+            .set_write_timeout(*config.get_write_timeout())
+            .await
+            .set_response_header(CONNECTION, CONNECTION_CLOSE)
+            .await
+            .send_response(status_code, "")
+            .await;
+    }
+
+    async fn send_if_auto_send(ctx: &Context, config: &ServerConfig<'_>) {
+        if let Err(err) = ctx.send_if_auto_send().await {
+            let error_request: ErrorRequest = ErrorRequest::from_context(ctx).await;
+            config.get_error_handle()(ErrorEvent::Write(error_request, err.to_string())).await;
+        }
+    }
+
+    async fn run_handler(ctx: &Context, next: &Next, config: &ServerConfig<'_>) -> bool {
+        let handler_future: CatchUnwind<_> = CatchUnwind::new(next.run(ctx.clone()));
+        let handler_result: ResultPanicPayload<()> = match *config.get_handler_timeout() {
+            Some(handler_timeout) => match timeout(handler_timeout, handler_future).await {
+                Ok(handler_result) => handler_result,
+                Err(_) => {
+                    let error_request: ErrorRequest = ErrorRequest::from_context(ctx).await;
+                    config.get_error_handle()(ErrorEvent::HandlerTimeout(error_request)).await;
+                    Self::send_handler_timeout(ctx).await;
+                    return false;
+                }
+            },
+            None => handler_future.await,
+        };
+        let Err(payload) = handler_result else {
+            return true;
+        };
+        let request_panic: RequestPanic = RequestPanic::from_payload(&payload);
+        let error_request: ErrorRequest = ErrorRequest::from_context(ctx).await;
+        config.get_error_handle()(ErrorEvent::Panic(error_request, request_panic)).await;
+        if !ctx.get_responded().await {
+            let _ = ctx
+                .set_response_header(CONNECTION, CONNECTION_CLOSE)
+                .await
+                .send_response(
+                    *config.get_panic_status_code(),
+                    config.get_panic_response_body().clone(),
+                )
+                .await;
+        }
+        false
+    }
+
+    async fn send_handler_timeout(ctx: &Context) {
+        if !ctx.get_responded().await {
+            let _ = ctx
+                .set_response_header(CONNECTION, CONNECTION_CLOSE)
+                .await
+                .send_response(503, "")
+                .await;
+        }
+    }
+
+    async fn handle_request_common<'a>(
+        handler: &RequestHandlerImmutableParams<'a>,
+        request: &Request,
+    ) -> bool {
+        let stream: &ArcRwLockStream = handler.stream;
+
+        let ctx: Context = Context::from_stream_request(stream, request);
+        ctx.set_request_id(next_request_id())
+            .await
+            .set_listener(handler.listener)
+            .await
+            .set_write_timeout(*handler.config.get_write_timeout())
+            .await;
+        let Ok(_in_flight_permit) = handler.config.try_acquire_in_flight() else {
+            let _ = ctx
+                .set_response_header(CONNECTION, CONNECTION_CLOSE)
+                .await
+                .send_response(503, "")
+                .await;
+            return false;
+        };
+        if handler.is_shutting_down() {
+            ctx.set_response_header(CONNECTION, CONNECTION_CLOSE).await;
+        }
+        if !Self::run_handler(&ctx, handler.next, handler.config).await {
+            return false;
+        }
+        if handler.is_shutting_down() && !ctx.get_responded().await {
+            ctx.set_response_header(CONNECTION, CONNECTION_CLOSE).await;
+        }
+        Self::send_if_auto_send(&ctx, handler.config).await;
+        yield_now().await;
+        request.is_enable_keep_alive() && !handler.is_shutting_down()
+    }
+
+    async fn match_route(
+        route_func: &ArcRwLockHashMapRouteMethods,
+        route_matcher: &ArcRwLockRouteMatcher,
+        route: &str,
+        method: &str,
+    ) -> RouteMatch {
+        if let Some(route_methods) = route_func.read().await.get(route) {
+            return route_methods.resolve(method, hash_map_xx_hash3_64());
+        }
+        if let Some((route_methods, params)) = route_matcher.read().await.match_route(route) {
+            return route_methods.resolve(method, params);
+        }
+        RouteMatch::NotFound
+    }
+
+    async fn match_fallback(
+        fallback: &ArcRwLockVecRouterFallback,
+        route: &str,
+    ) -> OptionRouterFallback {
+        fallback
+            .read()
+            .await
+            .iter()
+            .filter(|(prefix, _)| Router::is_prefix_match(prefix, route))
+            .max_by_key(|(prefix, _)| prefix.trim_end_matches(DEFAULT_HTTP_PATH).len())
+            .cloned()
+    }
+
+    async fn is_listener_allowed(
+        listener_route: &ArcRwLockVecListenerRoute,
+        route: &str,
+        listener: &str,
+    ) -> bool {
+        let listener_route: RwLockReadGuard<'_, VecListenerRoute> = listener_route.read().await;
+        let mut restriction = listener_route
+            .iter()
+            .filter(|(pattern, _)| pattern.is_match(route))
+            .peekable();
+        restriction.peek().is_none() || restriction.any(|(_, name)| name == listener)
+    }
+
+    async fn try_acquire_route_concurrency(
+        route_concurrency: &ArcRwLockVecRouteConcurrency,
+        route: &str,
+    ) -> OptionVecOwnedSemaphorePermit {
+        route_concurrency
+            .read()
+            .await
+            .iter()
+            .filter(|(pattern, _)| pattern.is_match(route))
+            .map(|(_, semaphore)| semaphore.clone().try_acquire_owned().ok())
+            .collect()
+    }
+
+    async fn match_route_middleware(
+        route_middleware: &ArcRwLockRouteMiddleware,
+        route: &str,
+    ) -> VecIndexArcFunc {
+        route_middleware
+            .read()
+            .await
+            .iter()
+            .enumerate()
+            .filter(|(_, (pattern, _))| pattern.is_match(route))
+            .map(|(index, (_, middleware))| (index, middleware.clone()))
+            .collect()
+    }
+
+    async fn handle_websocket_connection<'a>(
+        handler: &RequestHandlerImmutableParams<'a>,
+        first_request: &mut Request,
+    ) {
+        let stream: &ArcRwLockStream = handler.stream;
+        let buffer_size: usize = *handler.config.get_websocket_buffer_size();
+        let ctx: Context = Context::from_stream_request(stream, first_request);
+        if ctx.handle_websocket().await.is_err() {
+            return;
+        }
+        let route: &String = first_request.get_path();
+        let contains_disable_inner_websocket_handle: bool = handler
+            .config
+            .contains_disable_inner_websocket_handle(route)
+            .await;
+        if contains_disable_inner_websocket_handle {
+            while Self::handle_request_common(handler, first_request).await
+                && !handler.is_shutting_down()
+            {}
+            return;
+        }
+        while let Some(Ok(request)) = Self::until_shutdown(
+            handler.shutdown,
+            Request::websocket_request_from_stream(stream, buffer_size),
+        )
+        .await
+        {
+            let body: RequestBody = request.get_body().clone();
+            first_request.set_body(body);
+            let _ = Self::handle_request_common(handler, first_request).await;
+        }
+    }
+
+    async fn handle_http_connection<'a>(
+        handler: &RequestHandlerImmutableParams<'a>,
+        first_request: &Request,
+        read_buffer: &mut Vec<u8>,
+    ) {
+        let handle_result: bool = Self::handle_request_common(handler, first_request).await;
+        if !handle_result {
+            return;
+        }
+        let stream: ArcRwLockStream = handler.stream.clone();
+        let route: &String = first_request.get_path();
+        let contains_disable_inner_http_handle: bool = handler
+            .config
+            .contains_disable_inner_http_handle(route)
+            .await;
+        if contains_disable_inner_http_handle {
+            while Self::handle_request_common(handler, first_request).await
+                && !handler.is_shutting_down()
+            {}
+            return;
+        }
+        let keep_alive_timeout: OptionDuration = *handler.config.get_keep_alive_timeout();
+        loop {
+            let request_result: Option<ResultRequestRead> = Self::read_tcp_request_until_shutdown(
+                &stream,
+                handler.config,
+                read_buffer,
+                keep_alive_timeout,
+                handler.shutdown,
+            )
+            .await;
+            let request: Request = match request_result {
+                Some(Ok(request)) => request,
+                Some(Err(err)) => {
+                    Self::handle_read_error(&stream, handler.config, handler.listener, err).await;
+                    return;
+                }
+                None => return,
+            };
+            let handle_result: bool = Self::handle_request_common(handler, &request).await;
+            if !handle_result {
+                return;
+            }
+        }
+    }
+
+    pub(crate) async fn handle_stream_connection<S>(
+        stream: S,
+        connection_info: ConnectionInfo,
+        listener: String,
+        config: ServerConfig<'static>,
+        next: Next,
+        shutdown: WatchReceiverServerState,
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin + Send,
+    {
+        let (mut reader, mut writer) = split(stream);
+        let params: StreamHandlerParams =
+            StreamHandlerParams::new(&connection_info, &config, &next, &shutdown, &listener);
+        let mut read_buffer: Vec<u8> = vec![];
+        let mut idle_timeout: OptionDuration = None;
+        loop {
+            let request_result: Option<ResultRequestRead> = Self::read_http_request_until_shutdown(
+                &mut reader,
+                &config,
+                &mut read_buffer,
+                idle_timeout,
+                &shutdown,
+            )
+            .await;
+            let request: Request = match request_result {
+                Some(Ok(request)) => request,
+                Some(Err(err)) => {
+                    Self::handle_stream_read_error(&mut writer, &params, err).await;
+                    break;
+                }
+                None => break,
+            };
+            if request.get_upgrade_type().is_websocket() {
+                Self::handle_stream_websocket(
+                    &mut reader,
+                    &mut writer,
+                    &mut read_buffer,
+                    &params,
+                    &request,
+                )
+                .await;
+                break;
+            }
+            if !Self::handle_stream_request(&mut writer, &params, &request).await {
+                break;
+            }
+            idle_timeout = *config.get_keep_alive_timeout();
+        }
+        let _ = writer.shutdown().await;
+    }
+
+    async fn create_stream_context(params: &StreamHandlerParams<'_>, request: &Request) -> Context {
+        let ctx: Context = Context::detached();
+        ctx.set_request(request.clone())
+            .await
+            .set_request_id(next_request_id())
+            .await
+            .set_listener(params.listener)
+            .await
+            .set_write_timeout(*params.config.get_write_timeout())
+            .await;
+        Self::set_connection_info(&ctx, params.connection_info).await;
+        ctx
+    }
+
+    async fn set_connection_info(ctx: &Context, connection_info: &ConnectionInfo) {
+        ctx.set_peer_addr(connection_info.peer_addr)
+            .await
+            .set_peer_description(connection_info.peer_description.clone())
+            .await;
+        #[cfg(feature = "tls")]
+        ctx.set_tls_alpn_protocol(connection_info.tls_alpn_protocol.clone())
+            .await
+            .set_tls_peer_certificates(connection_info.tls_peer_certificates.clone())
+            .await;
+    }
+
+    async fn handle_stream_read_error<W>(
+        writer: &mut W,
+        params: &StreamHandlerParams<'_>,
+        err: RequestReadError,
+    ) where
+        W: AsyncWrite + Unpin,
+    {
+        let ctx: Context = Self::create_stream_context(params, &Request::default()).await;
+        let kind: StreamResponseKind = StreamResponseKind::Http11;
+        Self::respond_stream(writer, &ctx, params.config, kind, async {
+            Self::handle_context_read_error(&ctx, params.config, params.listener, err).await;
+            false
+        })
+        .await;
+    }
+
+    async fn handle_stream_request<W>(
+        writer: &mut W,
+        params: &StreamHandlerParams<'_>,
+        request: &Request,
+    ) -> bool
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let ctx: Context = Self::create_stream_context(params, request).await;
+        let kind: StreamResponseKind = StreamResponseKind::from_request(request);
+        Self::respond_stream(writer, &ctx, params.config, kind, async {
+            let Ok(_in_flight_permit) = params.config.try_acquire_in_flight() else {
+                let _ = ctx
+                    .set_response_header(CONNECTION, CONNECTION_CLOSE)
+                    .await
+                    .send_response(503, "")
+                    .await;
+                return false;
+            };
+            if params.is_shutting_down() {
+                ctx.set_response_header(CONNECTION, CONNECTION_CLOSE).await;
+            }
+            if !Self::run_handler(&ctx, params.next, params.config).await {
+                return false;
+            }
+            if params.is_shutting_down() && !ctx.get_responded().await {
+                ctx.set_response_header(CONNECTION, CONNECTION_CLOSE).await;
+            }
+            Self::send_if_auto_send(&ctx, params.config).await;
+            if !ctx.get_responded().await {
+                let _ = ctx.send().await;
+            }
+            request.is_enable_keep_alive() && !params.is_shutting_down()
+        })
+        .await
+    }
+
+    async fn handle_stream_websocket<R, W>(
+        reader: &mut R,
+        writer: &mut W,
+        read_buffer: &mut Vec<u8>,
+        params: &StreamHandlerParams<'_>,
+        request: &Request,
+    ) where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let write_timeout: OptionDuration = *params.config.get_write_timeout();
+        let max_body_size: OptionUsize = *params.config.get_max_body_size();
+        let ctx: Context = Self::create_stream_context(params, request).await;
+        let kind: StreamResponseKind = StreamResponseKind::Http11;
+        let handshake_future = async { ctx.handle_websocket().await.is_ok() };
+        if !Self::respond_stream(writer, &ctx, params.config, kind, handshake_future).await {
+            return;
+        }
+        let mut message: Vec<u8> = vec![];
+        while let Some(frame_result) = Self::until_shutdown(
+            params.shutdown,
+            Self::read_websocket_frame(reader, read_buffer, params.config),
+        )
+        .await
+        {
+            let (is_final, opcode, payload): WebSocketFrameParts = match frame_result {
+                Ok(frame) => frame,
+                Err(RequestReadError::LimitExceeded(_)) => {
+                    let payload: [u8; 2] = WEBSOCKET_CLOSE_MESSAGE_TOO_BIG.to_be_bytes();
+                    let frame: Vec<u8> =
+                        encode_websocket_frame(WEBSOCKET_OPCODE_CLOSE, &payload, None);
+                    let _ = Self::write_stream_data(writer, write_timeout, &frame).await;
+                    return;
+                }
+                Err(_) => return,
+            };
+            match opcode {
+                WEBSOCKET_OPCODE_CLOSE => {
+                    let frame: Vec<u8> =
+                        encode_websocket_frame(WEBSOCKET_OPCODE_CLOSE, &payload, None);
+                    let _ = Self::write_stream_data(writer, write_timeout, &frame).await;
+                    return;
+                }
+                WEBSOCKET_OPCODE_PING => {
+                    let frame: Vec<u8> =
+                        encode_websocket_frame(WEBSOCKET_OPCODE_PONG, &payload, None);
+                    if !Self::write_stream_data(writer, write_timeout, &frame).await {
+                        return;
+                    }
+                    continue;
+                }
+                WEBSOCKET_OPCODE_PONG => continue,
+                _ => message.extend(payload),
+            }
+            if max_body_size.is_some_and(|max: usize| message.len() > max) {
+                let payload: [u8; 2] = WEBSOCKET_CLOSE_MESSAGE_TOO_BIG.to_be_bytes();
+                let frame: Vec<u8> = encode_websocket_frame(WEBSOCKET_OPCODE_CLOSE, &payload, None);
+                let _ = Self::write_stream_data(writer, write_timeout, &frame).await;
+                return;
+            }
+            if !is_final {
+                continue;
+            }
+            let mut message_request: Request = request.clone();
+            message_request.set_body(take(&mut message));
+            let message_ctx: Context = Self::create_stream_context(params, &message_request).await;
+            let kind: StreamResponseKind = StreamResponseKind::WebSocket;
+            let message_future = async {
+                if Self::run_handler(&message_ctx, params.next, params.config).await {
+                    Self::send_if_auto_send(&message_ctx, params.config).await;
+                }
+                true
+            };
+            if !Self::respond_stream(writer, &message_ctx, params.config, kind, message_future)
+                .await
+            {
+                return;
+            }
+        }
+    }
+
+    async fn read_websocket_frame<R>(
+        reader: &mut R,
+        read_buffer: &mut Vec<u8>,
+        config: &ServerConfig<'_>,
+    ) -> ResultWebSocketFrameParts
+    where
+        R: AsyncRead + Unpin,
+    {
+        let buffer_size: usize = *config.get_websocket_buffer_size();
+        let max_body_size: OptionUsize = *config.get_max_body_size();
+        loop {
+            if let Some(frame_size) = websocket_frame_size(read_buffer) {
+                if max_body_size.is_some_and(|max: usize| {
+                    frame_size > max.saturating_add(WEBSOCKET_MAX_FRAME_HEAD_SIZE)
+                }) {
+                    return Err(RequestReadError::LimitExceeded(413));
+                }
+                if let Some((frame, size)) = decode_websocket_frame(read_buffer) {
+                    read_buffer.drain(..size);
+                    return Ok(frame);
+                }
+            }
+            Self::fill_read_buffer(reader, read_buffer, buffer_size).await?;
+        }
+    }
+
+    async fn respond_stream<W, F>(
+        writer: &mut W,
+        ctx: &Context,
+        config: &ServerConfig<'_>,
+        kind: StreamResponseKind,
+        handler_future: F,
+    ) -> bool
+    where
+        W: AsyncWrite + Unpin,
+        F: Future<Output = bool>,
+    {
+        let (response_sender, response_receiver) = mpsc::channel(RESPONSE_FRAME_CAPACITY);
+        ctx.set_response_sender(Some(response_sender)).await;
+        let handler_future = async {
+            let output: bool = handler_future.await;
+            ctx.set_response_sender(None).await;
+            output
+        };
+        let (output, written): (bool, bool) = join!(
+            handler_future,
+            Self::write_stream_response(response_receiver, writer, config, kind)
+        );
+        output && written
+    }
+
+    async fn write_stream_response<W>(
+        mut response_receiver: ResponseFrameReceiver,
+        writer: &mut W,
+        config: &ServerConfig<'_>,
+        kind: StreamResponseKind,
+    ) -> bool
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let write_timeout: OptionDuration = *config.get_write_timeout();
+        let mut head_written: bool = kind == StreamResponseKind::WebSocket;
+        let mut keep_alive: bool = true;
+        let mut body_kind: StreamBodyKind = match kind {
+            StreamResponseKind::WebSocket => StreamBodyKind::WebSocket,
+            _ => StreamBodyKind::Empty,
+        };
+        while let Some(response_frame) = response_receiver.recv().await {
+            let data: Vec<u8> = match response_frame {
+                ResponseFrame::Head(status_code, mut headers) => {
+                    if head_written {
+                        continue;
+                    }
+                    head_written = true;
+                    (body_kind, keep_alive) =
+                        Self::stream_body_kind(status_code, &mut headers, kind);
+                    format_response_head(status_code, &headers)
+                }
+                ResponseFrame::Body(body) => {
+                    if !head_written {
+                        continue;
+                    }
+                    Self::encode_stream_body(&mut body_kind, body)
+                }
+                ResponseFrame::End => {
+                    keep_alive = false;
+                    break;
+                }
+            };
+            if data.is_empty() {
+                continue;
+            }
+            if !Self::write_stream_data(writer, write_timeout, &data).await {
+                return false;
+            }
+        }
+        if body_kind == StreamBodyKind::Chunked
+            && !Self::write_stream_data(writer, write_timeout, CHUNKED_BODY_END).await
+        {
+            return false;
+        }
+        keep_alive
+            && head_written
+            && !matches!(body_kind, StreamBodyKind::Length(remaining) if remaining > 0)
+    }
+
+    pub(crate) fn stream_body_kind(
+        status_code: ResponseStatusCode,
+        headers: &mut ResponseHeaders,
+        kind: StreamResponseKind,
+    ) -> StreamBodyState {
+        let keep_alive: bool = !headers.iter().any(|(key, value)| {
+            key.eq_ignore_ascii_case(CONNECTION) && value.eq_ignore_ascii_case(CONNECTION_CLOSE)
+        });
+        if kind == StreamResponseKind::Head || !status_has_body(status_code) {
+            return (StreamBodyKind::Empty, keep_alive);
+        }
+        let content_length: OptionUsize = headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(CONTENT_LENGTH))
+            .and_then(|(_, value)| value.trim().parse().ok());
+        headers.retain(|key, _| !key.eq_ignore_ascii_case(TRANSFER_ENCODING));
+        match (content_length, kind) {
+            (Some(content_length), _) => (StreamBodyKind::Length(content_length), keep_alive),
+            (None, StreamResponseKind::Http11) => {
+                headers.insert(TRANSFER_ENCODING.to_string(), CHUNKED.to_string());
+                (StreamBodyKind::Chunked, keep_alive)
+            }
+            (None, _) => (StreamBodyKind::Close, false),
+        }
+    }
+
+    pub(crate) fn encode_stream_body(
+        body_kind: &mut StreamBodyKind,
+        mut body: ResponseBody,
+    ) -> Vec<u8> {
+        if body.is_empty() {
+            return body;
+        }
+        match body_kind {
+            StreamBodyKind::Empty => vec![],
+            StreamBodyKind::Length(remaining) => {
+                body.truncate(*remaining);
+                *remaining -= body.len();
+                body
+            }
+            StreamBodyKind::Chunked => {
+                let mut data: Vec<u8> = format!("{:x}\r\n", body.len()).into_bytes();
+                data.extend_from_slice(&body);
+                data.extend_from_slice(HTTP_LINE_END);
+                data
+            }
+            StreamBodyKind::Close => body,
+            StreamBodyKind::WebSocket => {
+                let opcode: u8 = match std::str::from_utf8(&body) {
+                    Ok(_) => WEBSOCKET_OPCODE_TEXT,
+                    Err(_) => WEBSOCKET_OPCODE_BINARY,
+                };
+                encode_websocket_frame(opcode, &body, None)
+            }
+        }
+    }
+
+    async fn write_stream_data<W>(
+        writer: &mut W,
+        write_timeout: OptionDuration,
+        data: &[u8],
+    ) -> bool
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let write = async {
+            writer.write_all(data).await?;
+            writer.flush().await
+        };
+        let write_result: IoResult<()> = match write_timeout {
+            Some(write_timeout) => timeout(write_timeout, write)
+                .await
+                .unwrap_or_else(|_| Err(ErrorKind::TimedOut.into())),
+            None => write.await,
+        };
+        write_result.is_ok()
+    }
+}
+
+impl<'a> RequestHandlerImmutableParams<'a> {
+    pub fn new(
         stream: &'a ArcRwLockStream,
         config: &'a ServerConfig<'a>,
-        request_middleware: &'a ArcRwLockMiddlewareFuncBox,
-        response_middleware: &'a ArcRwLockMiddlewareFuncBox,
-        route_func: &'a ArcRwLockHashMapRouteFuncBox,
-        route_matcher: &'a ArcRwLock<RouteMatcher>,
+        next: &'a Next,
+        shutdown: &'a WatchReceiverServerState,
+        listener: &'a str,
     ) -> Self {
         Self {
             stream,
             config,
-            request_middleware,
-            response_middleware,
-            route_func,
-            route_matcher,
+            next,
+            shutdown,
+            listener,
+        }
+    }
+
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.shutdown.borrow().is_shutting_down()
+    }
+}
+
+impl<'a> StreamHandlerParams<'a> {
+    pub(crate) fn new(
+        connection_info: &'a ConnectionInfo,
+        config: &'a ServerConfig<'a>,
+        next: &'a Next,
+        shutdown: &'a WatchReceiverServerState,
+        listener: &'a str,
+    ) -> Self {
+        Self {
+            connection_info,
+            config,
+            next,
+            shutdown,
+            listener,
+        }
+    }
+
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.shutdown.borrow().is_shutting_down()
+    }
+}
+
+impl AsyncRead for TcpStreamReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<IoResult<()>> {
+        loop {
+            match self.0.poll_read_ready(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+            match self.0.try_read(buf.initialize_unfilled()) {
+                Ok(size) => {
+                    buf.advance(size);
+                    return Poll::Ready(Ok(()));
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => continue,
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+}
+
+impl StreamResponseKind {
+    pub(crate) fn from_request(request: &Request) -> Self {
+        if request.get_method().to_string() == HEAD {
+            return Self::Head;
+        }
+        match request.get_version().is_http1_1_or_higher() {
+            true => Self::Http11,
+            false => Self::Http10,
+        }
+    }
+}
+
+#[cfg(feature = "http2")]
+impl RewindStream {
+    pub(crate) fn new(stream: TcpStream, buffer: Vec<u8>) -> Self {
+        Self { buffer, stream }
+    }
+}
+
+#[cfg(feature = "http2")]
+impl AsyncRead for RewindStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<IoResult<()>> {
+        if self.buffer.is_empty() {
+            return Pin::new(&mut self.stream).poll_read(cx, buf);
+        }
+        let size: usize = self.buffer.len().min(buf.remaining());
+        buf.put_slice(&self.buffer[..size]);
+        self.buffer.drain(..size);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "http2")]
+impl AsyncWrite for RewindStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+impl ServerListener {
+    pub(crate) async fn accept(&self) -> IoResult<ServerConnection> {
+        match self {
+            Self::Tcp(tcp_listener) => {
+                let (stream, _socket_addr) = tcp_listener.accept().await?;
+                Ok(ServerConnection::Tcp(stream))
+            }
+            #[cfg(feature = "tls")]
+            Self::Tls(tcp_listener, tls_acceptor) => {
+                let (stream, _socket_addr) = tcp_listener.accept().await?;
+                Ok(ServerConnection::Tls(stream, tls_acceptor.clone()))
+            }
+            #[cfg(unix)]
+            Self::Unix(unix_listener, path) => {
+                let (stream, _socket_addr) = unix_listener.accept().await?;
+                let peer_description: String = unix_peer_description(&stream, path);
+                Ok(ServerConnection::Unix(stream, peer_description))
+            }
+        }
+    }
+
+    pub(crate) fn close(self) {
+        match self {
+            Self::Tcp(tcp_listener) => drop(tcp_listener),
+            #[cfg(feature = "tls")]
+            Self::Tls(tcp_listener, _) => drop(tcp_listener),
+            #[cfg(unix)]
+            Self::Unix(unix_listener, path) => {
+                drop(unix_listener);
+                let _ = remove_file(path);
+            }
+        }
+    }
+}
+
+impl ServerConnection {
+    pub(crate) fn get_tcp_stream(&self) -> Option<&TcpStream> {
+        match self {
+            Self::Tcp(stream) => Some(stream),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream, _) => Some(stream),
+            #[cfg(unix)]
+            Self::Unix(..) => None,
+        }
+    }
+}
+
+impl From<IoError> for RequestReadError {
+    fn from(err: IoError) -> Self {
+        match err.kind() {
+            ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted => Self::Closed,
+            _ => Self::Io(err.to_string()),
+        }
+    }
+}
+
+impl IntoStdTcpListener for StdTcpListener {
+    fn into_std_tcp_listener(self) -> IoResult<StdTcpListener> {
+        Ok(self)
+    }
+}
+
+impl IntoStdTcpListener for TcpListener {
+    fn into_std_tcp_listener(self) -> IoResult<StdTcpListener> {
+        self.into_std()
+    }
+}
+
+impl ServerState {
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        matches!(self, Self::ShuttingDown(_))
+    }
+
+    pub(crate) fn get_timeout(&self) -> OptionDuration {
+        match self {
+            Self::ShuttingDown(drain_timeout) => *drain_timeout,
+            Self::Running => None,
+        }
+    }
+}
+
+impl ServerHandle {
+    fn begin_shutdown(&self, drain_timeout: OptionDuration) {
+        begin_shutdown(self.get_state_sender(), drain_timeout);
+    }
+
+    pub async fn shutdown(&self) {
+        self.begin_shutdown(None);
+        self.drained().await;
+    }
+
+    pub async fn shutdown_with_timeout(&self, drain_timeout: Duration) {
+        self.begin_shutdown(Some(drain_timeout));
+        self.drained().await;
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.get_local_addrs()[0].1
+    }
+
+    pub fn unix_socket_path(&self) -> Option<&Path> {
+        self.get_unix_socket().as_deref()
+    }
+
+    pub fn listener_addr<N>(&self, name: N) -> OptionSocketAddr
+    where
+        N: AsRef<str>,
+    {
+        self.get_local_addrs()
+            .iter()
+            .find(|(listener, _)| listener == name.as_ref())
+            .map(|(_, local_addr)| *local_addr)
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.get_state_sender().borrow().is_shutting_down()
+            && !*self.get_drained_receiver().borrow()
+    }
+
+    pub async fn wait(&self) -> ServerResult {
+        self.drained().await;
+        match self.get_error().read().await.clone() {
+            Some(err) => Err(err),
+            None => Ok(()),
         }
     }
+
+    pub async fn drained(&self) {
+        let mut drained_receiver: WatchReceiverBool = self.get_drained_receiver().clone();
+        let _ = drained_receiver.wait_for(|drained: &bool| *drained).await;
+    }
 }
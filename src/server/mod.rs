@@ -1,6 +1,16 @@
+pub(crate) mod cfg;
+pub(crate) mod r#const;
+pub(crate) mod r#enum;
+pub(crate) mod r#fn;
 pub(crate) mod r#impl;
 pub(crate) mod r#struct;
+pub(crate) mod r#trait;
 pub(crate) mod r#type;
 
 pub use r#struct::*;
+pub use r#trait::*;
 pub use r#type::*;
+
+pub(crate) use r#const::*;
+pub(crate) use r#enum::*;
+pub(crate) use r#fn::*;
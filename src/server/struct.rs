@@ -7,7 +7,7 @@ pub struct Server {
     pub(super) config: ArcRwLockServerConfig<'static>,
     #[get(pub(crate))]
     #[set(pub(crate))]
-    pub(super) route: ArcRwLockHashMapRouteFuncBox,
+    pub(super) route: ArcRwLockHashMapRouteMethods,
     #[get(pub(crate))]
     #[set(pub(crate))]
     pub(super) route_matcher: ArcRwLockRouteMatcher,
@@ -16,15 +16,73 @@ pub struct Server {
     pub(super) request_middleware: ArcRwLockMiddlewareFuncBox,
     #[get(pub(crate))]
     #[set(pub(crate))]
+    pub(super) route_middleware: ArcRwLockRouteMiddleware,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
     pub(super) response_middleware: ArcRwLockMiddlewareFuncBox,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) middleware: ArcRwLockVecArcMiddlewareFunc,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) fallback: ArcRwLockVecRouterFallback,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) listener_route: ArcRwLockVecListenerRoute,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) pending_listener: ArcRwLockVecPendingListener,
+    #[get(pub(crate))]
+    #[set(pub(crate))]
+    pub(super) route_concurrency: ArcRwLockVecRouteConcurrency,
 }
 
 #[derive(Clone)]
 pub(crate) struct RequestHandlerImmutableParams<'a> {
     pub(super) stream: &'a ArcRwLockStream,
     pub(super) config: &'a ServerConfig<'a>,
-    pub(super) request_middleware: &'a ArcRwLockMiddlewareFuncBox,
-    pub(super) response_middleware: &'a ArcRwLockMiddlewareFuncBox,
-    pub(super) route_func: &'a ArcRwLockHashMapRouteFuncBox,
-    pub(super) route_matcher: &'a ArcRwLockRouteMatcher,
+    pub(super) next: &'a Next,
+    pub(super) shutdown: &'a WatchReceiverServerState,
+    pub(super) listener: &'a str,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct ConnectionInfo {
+    pub(super) peer_addr: OptionSocketAddr,
+    pub(super) peer_description: OptionString,
+    #[cfg(feature = "tls")]
+    pub(super) tls_alpn_protocol: OptionString,
+    #[cfg(feature = "tls")]
+    pub(super) tls_peer_certificates: VecVecU8,
+}
+
+#[derive(Clone)]
+pub(crate) struct StreamHandlerParams<'a> {
+    pub(super) connection_info: &'a ConnectionInfo,
+    pub(super) config: &'a ServerConfig<'a>,
+    pub(super) next: &'a Next,
+    pub(super) shutdown: &'a WatchReceiverServerState,
+    pub(super) listener: &'a str,
+}
+
+pub(crate) struct TcpStreamReader<'a>(pub(super) &'a TcpStream);
+
+#[cfg(feature = "http2")]
+pub(crate) struct RewindStream {
+    pub(super) buffer: Vec<u8>,
+    pub(super) stream: TcpStream,
+}
+
+#[derive(Clone, Getter)]
+pub struct ServerHandle {
+    #[get(pub(crate))]
+    pub(super) local_addrs: VecListenerSocketAddr,
+    #[get(pub(crate))]
+    pub(super) state_sender: ArcWatchSenderServerState,
+    #[get(pub(crate))]
+    pub(super) drained_receiver: WatchReceiverBool,
+    #[get(pub(crate))]
+    pub(super) error: ArcRwLockOptionServerError,
+    #[get(pub(crate))]
+    pub(super) unix_socket: OptionPathBuf,
 }
@@ -0,0 +1,5 @@
+use crate::*;
+
+pub trait IntoStdTcpListener {
+    fn into_std_tcp_listener(self) -> IoResult<StdTcpListener>;
+}
@@ -1,3 +1,42 @@
 use crate::*;
 
 pub type ServerResult = Result<(), ServerError>;
+pub type ResultServerHandle = Result<ServerHandle, ServerError>;
+
+pub(crate) type WatchSenderServerState = watch::Sender<ServerState>;
+pub(crate) type ArcWatchSenderServerState = Arc<WatchSenderServerState>;
+pub(crate) type WatchReceiverServerState = watch::Receiver<ServerState>;
+pub(crate) type WatchReceiverBool = watch::Receiver<bool>;
+pub(crate) type JoinSetConnection = JoinSet<()>;
+pub(crate) type JoinSetServerResult = JoinSet<ServerResult>;
+pub(crate) type OptionServerError = Option<ServerError>;
+pub(crate) type ArcRwLockOptionServerError = ArcRwLock<OptionServerError>;
+pub(crate) type ListenerRoute = (RoutePattern, String);
+pub(crate) type VecListenerRoute = Vec<ListenerRoute>;
+pub(crate) type ArcRwLockVecListenerRoute = ArcRwLock<VecListenerRoute>;
+pub(crate) type ListenerTcpListener = (String, TcpListener);
+pub(crate) type VecListenerTcpListener = Vec<ListenerTcpListener>;
+pub(crate) type ListenerServerListener = (String, ServerListener);
+pub(crate) type VecListenerServerListener = Vec<ListenerServerListener>;
+pub(crate) type ListenerSocketAddr = (String, SocketAddr);
+pub(crate) type VecListenerSocketAddr = Vec<ListenerSocketAddr>;
+pub(crate) type PendingListener = (String, IoResult<StdTcpListener>);
+pub(crate) type VecPendingListener = Vec<PendingListener>;
+pub(crate) type ArcRwLockVecPendingListener = ArcRwLock<VecPendingListener>;
+#[cfg(feature = "http2")]
+pub(crate) type OptionHttp2SendStream = Option<SendStream<Bytes>>;
+pub(crate) type ResultRequestRead = Result<Request, RequestReadError>;
+pub(crate) type ResultRequestBody = Result<RequestBody, RequestReadError>;
+pub(crate) type RequestHeaderPair = (String, String);
+pub(crate) type VecRequestHeaderPair = Vec<RequestHeaderPair>;
+pub(crate) type ParsedRequestHead = (Request, RequestBodyKind);
+pub(crate) type ResultParsedRequestHead = Result<ParsedRequestHead, RequestReadError>;
+pub(crate) type RouteConcurrency = (RoutePattern, ArcSemaphore);
+pub(crate) type VecRouteConcurrency = Vec<RouteConcurrency>;
+pub(crate) type ArcRwLockVecRouteConcurrency = ArcRwLock<VecRouteConcurrency>;
+pub(crate) type VecOwnedSemaphorePermit = Vec<OwnedSemaphorePermit>;
+pub(crate) type OptionVecOwnedSemaphorePermit = Option<VecOwnedSemaphorePermit>;
+pub(crate) type WebSocketFrameParts = (bool, u8, Vec<u8>);
+pub(crate) type OptionWebSocketFrameParts = Option<(WebSocketFrameParts, usize)>;
+pub(crate) type ResultWebSocketFrameParts = Result<WebSocketFrameParts, RequestReadError>;
+pub(crate) type StreamBodyState = (StreamBodyKind, bool);
@@ -0,0 +1,179 @@
+use crate::*;
+
+#[test]
+fn test_static_file_helpers() {
+    assert_eq!(http_date(784111777), "Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(
+        parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+        Some(784111777)
+    );
+    assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+    assert_eq!(
+        parse_range("bytes=0-4", 10),
+        RangeRequest::Partial(vec![(0, 4)])
+    );
+    assert_eq!(
+        parse_range("bytes=-3", 10),
+        RangeRequest::Partial(vec![(7, 9)])
+    );
+    assert_eq!(
+        parse_range("bytes=0-1, 8-", 10),
+        RangeRequest::Partial(vec![(0, 1), (8, 9)])
+    );
+    assert_eq!(
+        parse_range("bytes=5-8, 0-2, 3-4, 7-", 10),
+        RangeRequest::Partial(vec![(0, 9)])
+    );
+    assert_eq!(
+        parse_range("bytes=0-1, 0-1, 4-5", 10),
+        RangeRequest::Partial(vec![(0, 1), (4, 5)])
+    );
+    assert_eq!(parse_range("bytes=20-", 10), RangeRequest::Unsatisfiable);
+    assert_eq!(parse_range("bytes=5-1", 10), RangeRequest::Full);
+    assert_eq!(parse_range("items=0-1", 10), RangeRequest::Full);
+    assert_eq!(
+        sanitize_path("css/%73ite.css"),
+        Some(PathBuf::from("css/site.css"))
+    );
+    assert_eq!(sanitize_path("../secret"), None);
+    assert_eq!(sanitize_path("%2e%2e/secret"), None);
+    assert!(etag_matches("W/\"a\", \"b\"", "\"b\""));
+    assert!(accepts_encoding("gzip, br;q=1.0", "br"));
+    assert!(!accepts_encoding("gzip, br;q=0", "br"));
+}
+
+#[tokio::test]
+async fn test_static_files() {
+    let root: PathBuf =
+        std::env::temp_dir().join(format!("hyperlane-static-{}", std::process::id()));
+    let public: PathBuf = root.join("public");
+    fs::create_dir_all(public.join("docs")).await.unwrap();
+    fs::write(root.join("secret.txt"), "secret").await.unwrap();
+    fs::write(public.join("hello.txt"), "hello world")
+        .await
+        .unwrap();
+    fs::write(public.join("hello.txt.gz"), "gzipped")
+        .await
+        .unwrap();
+    fs::write(public.join("docs/index.html"), "<h1>docs</h1>")
+        .await
+        .unwrap();
+    let large: Vec<u8> = (0..STATIC_FILE_CHUNK_SIZE * 2 + 7)
+        .map(|index: usize| (index % 251) as u8)
+        .collect();
+    fs::write(public.join("large.bin"), &large).await.unwrap();
+    let server: Server = Server::new();
+    let mut static_files: StaticFiles = StaticFiles::new(&public);
+    static_files.set_precompressed(true);
+    server.static_files_with("/assets", static_files).await;
+    let client: TestClient = server.test_client();
+    let response: TestResponse = client.get("/assets/hello.txt").send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(response.get_body_string(), "hello world");
+    assert_eq!(
+        response.get_header(CONTENT_TYPE),
+        Some(TEXT_PLAIN.to_string())
+    );
+    let etag: String = response.get_header(ETAG).unwrap();
+    let last_modified: String = response.get_header(LAST_MODIFIED).unwrap();
+    let response: TestResponse = client.request(HEAD, "/assets/hello.txt").send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(response.get_header(CONTENT_LENGTH), Some("11".to_string()));
+    assert!(response.get_body().is_empty());
+    let response: TestResponse = client.get("/assets/large.bin").send().await;
+    assert_eq!(*response.get_status_code(), 200);
+    assert_eq!(*response.get_body(), large);
+    let response: TestResponse = client
+        .get("/assets/large.bin")
+        .header(RANGE, format!("bytes={}-", STATIC_FILE_CHUNK_SIZE - 3))
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 206);
+    assert_eq!(response.get_body()[..], large[STATIC_FILE_CHUNK_SIZE - 3..]);
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(RANGE, "bytes=0-4, 2-6, 7-")
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 206);
+    assert_eq!(response.get_body_string(), "hello world");
+    assert_eq!(
+        response.get_header(CONTENT_RANGE),
+        Some("bytes 0-10/11".to_string())
+    );
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(IF_NONE_MATCH, &etag)
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 304);
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(IF_MODIFIED_SINCE, &last_modified)
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 304);
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(RANGE, "bytes=0-4")
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 206);
+    assert_eq!(response.get_body_string(), "hello");
+    assert_eq!(
+        response.get_header(CONTENT_RANGE),
+        Some("bytes 0-4/11".to_string())
+    );
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(RANGE, "bytes=0-1,-5")
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 206);
+    let body: String = response.get_body_string();
+    assert!(body.contains(&format!("{}: bytes 0-1/11\r\n\r\nhe\r\n", CONTENT_RANGE)));
+    assert!(body.contains(&format!(
+        "{}: bytes 6-10/11\r\n\r\nworld\r\n",
+        CONTENT_RANGE
+    )));
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(RANGE, "bytes=50-")
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 416);
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(ACCEPT_ENCODING, "gzip")
+        .send()
+        .await;
+    assert_eq!(response.get_body_string(), "gzipped");
+    assert_eq!(
+        response.get_header(CONTENT_ENCODING),
+        Some(GZIP.to_string())
+    );
+    assert_eq!(response.get_header(CONTENT_LENGTH), Some("7".to_string()));
+    let gzip_etag: String = response.get_header(ETAG).unwrap();
+    let response: TestResponse = client
+        .request(HEAD, "/assets/hello.txt")
+        .header(ACCEPT_ENCODING, "gzip")
+        .send()
+        .await;
+    assert_eq!(response.get_header(CONTENT_LENGTH), Some("7".to_string()));
+    assert!(response.get_body().is_empty());
+    let response: TestResponse = client
+        .get("/assets/hello.txt")
+        .header(ACCEPT_ENCODING, "gzip")
+        .header(IF_NONE_MATCH, &gzip_etag)
+        .send()
+        .await;
+    assert_eq!(*response.get_status_code(), 304);
+    assert!(response.get_body().is_empty());
+    let response: TestResponse = client.get("/assets/docs/").send().await;
+    assert_eq!(response.get_body_string(), "<h1>docs</h1>");
+    let response: TestResponse = client.get("/assets/%2e%2e/secret.txt").send().await;
+    assert_eq!(*response.get_status_code(), 404);
+    let response: TestResponse = client.get("/assets/missing.txt").send().await;
+    assert_eq!(*response.get_status_code(), 404);
+    let _ = fs::remove_dir_all(&root).await;
+}
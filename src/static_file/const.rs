@@ -0,0 +1,15 @@
+pub(crate) const STATIC_FILE_PARAM: &str = "static_file_path";
+pub(crate) const DEFAULT_INDEX_FILE: &str = "index.html";
+pub(crate) const PARENT_DIR: &str = "..";
+pub(crate) const CURRENT_DIR: &str = ".";
+pub(crate) const BYTES_RANGE_PREFIX: &str = "bytes=";
+pub(crate) const MAX_RANGE_COUNT: usize = 16;
+pub(crate) const STATIC_FILE_CHUNK_SIZE: usize = 64 * 1024;
+pub(crate) const MULTIPART_BYTERANGES: &str = "multipart/byteranges; boundary=";
+pub(crate) const MULTIPART_BOUNDARY_PREFIX: &str = "hyperlane-";
+pub(crate) const PRECOMPRESSED_ENCODINGS: &[(&str, &str)] = &[("br", "br"), ("gzip", "gz")];
+pub(crate) const SECONDS_PER_DAY: u64 = 86_400;
+pub(crate) const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
@@ -0,0 +1,14 @@
+use crate::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RangeRequest {
+    Full,
+    Unsatisfiable,
+    Partial(VecByteRange),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StaticFilePart {
+    Bytes(Vec<u8>),
+    File(u64, u64),
+}
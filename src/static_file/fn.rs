@@ -0,0 +1,183 @@
+use crate::*;
+
+pub(crate) fn percent_decode(data: &str) -> OptionString {
+    let bytes: &[u8] = data.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex: &str = data.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+            continue;
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8(decoded).ok()
+}
+
+pub(crate) fn sanitize_path(request_path: &str) -> OptionPathBuf {
+    let decoded: String = percent_decode(request_path)?;
+    let mut path: PathBuf = PathBuf::new();
+    for segment in decoded.split(DEFAULT_HTTP_PATH) {
+        if segment.is_empty() || segment == CURRENT_DIR {
+            continue;
+        }
+        if segment == PARENT_DIR || segment.contains(['\\', '\0', ':']) {
+            return None;
+        }
+        path.push(segment);
+    }
+    Some(path)
+}
+
+pub(crate) fn content_type_for(path: &Path) -> &'static str {
+    let extension: String = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match FileExtension::parse(&extension).get_content_type() {
+        "" => APPLICATION_OCTET_STREAM,
+        content_type => content_type,
+    }
+}
+
+pub(crate) fn accepts_encoding(accept_encoding: &str, encoding: &str) -> bool {
+    accept_encoding.split(',').any(|item: &str| {
+        let mut params = item.split(';').map(str::trim);
+        let is_encoding: bool = params
+            .next()
+            .is_some_and(|name: &str| name.eq_ignore_ascii_case(encoding));
+        let is_refused: bool = params.any(|param: &str| {
+            param
+                .strip_prefix("q=")
+                .and_then(|quality: &str| quality.parse::<f32>().ok())
+                .is_some_and(|quality: f32| quality <= 0.0)
+        });
+        is_encoding && !is_refused
+    })
+}
+
+pub(crate) fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let if_none_match: &str = if_none_match.trim();
+    if if_none_match == "*" {
+        return true;
+    }
+    let etag: &str = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .any(|item: &str| item.trim().trim_start_matches("W/") == etag)
+}
+
+pub(crate) fn parse_range(range: &str, len: u64) -> RangeRequest {
+    let Some(spec) = range.trim().strip_prefix(BYTES_RANGE_PREFIX) else {
+        return RangeRequest::Full;
+    };
+    let mut ranges: VecByteRange = vec![];
+    for part in spec.split(',').map(str::trim) {
+        let Some((start, end)) = part.split_once('-') else {
+            return RangeRequest::Full;
+        };
+        let (start, end): (&str, &str) = (start.trim(), end.trim());
+        if start.is_empty() {
+            let Ok(suffix) = end.parse::<u64>() else {
+                return RangeRequest::Full;
+            };
+            if suffix > 0 && len > 0 {
+                ranges.push((len.saturating_sub(suffix), len - 1));
+            }
+            continue;
+        }
+        let Ok(start) = start.parse::<u64>() else {
+            return RangeRequest::Full;
+        };
+        let end: u64 = match end {
+            "" => u64::MAX,
+            end => match end.parse::<u64>() {
+                Ok(end) if end >= start => end,
+                _ => return RangeRequest::Full,
+            },
+        };
+        if start < len {
+            ranges.push((start, end.min(len - 1)));
+        }
+    }
+    match ranges.len() {
+        0 => RangeRequest::Unsatisfiable,
+        count if count > MAX_RANGE_COUNT => RangeRequest::Full,
+        _ => RangeRequest::Partial(merge_ranges(ranges)),
+    }
+}
+
+pub(crate) fn merge_ranges(mut ranges: VecByteRange) -> VecByteRange {
+    ranges.sort_unstable();
+    let mut merged: VecByteRange = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+pub(crate) fn http_date(timestamp: u64) -> String {
+    let days: u64 = timestamp / SECONDS_PER_DAY;
+    let seconds: u64 = timestamp % SECONDS_PER_DAY;
+    let (year, month, day): (i64, u64, u64) = civil_from_days(days as i64);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAY_NAMES[((days + 4) % 7) as usize],
+        day,
+        MONTH_NAMES[(month - 1) as usize],
+        year,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+pub(crate) fn parse_http_date(date: &str) -> Option<u64> {
+    let parts: Vec<&str> = date.split_whitespace().collect();
+    let [_, day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+    let day: u64 = day.parse().ok()?;
+    let month: u64 = MONTH_NAMES.iter().position(|name| name == month)? as u64 + 1;
+    let year: i64 = year.parse().ok()?;
+    let mut time = time.split(':').map(|value: &str| value.parse::<u64>().ok());
+    let (hour, minute, second): (u64, u64, u64) = (time.next()??, time.next()??, time.next()??);
+    let days: u64 = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second)
+}
+
+pub(crate) fn civil_from_days(days: i64) -> (i64, u64, u64) {
+    let days: i64 = days + 719_468;
+    let era: i64 = days.div_euclid(146_097);
+    let day_of_era: i64 = days.rem_euclid(146_097);
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: u64 = (day_of_year - (153 * month_index + 2) / 5 + 1) as u64;
+    let month: u64 = (if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    }) as u64;
+    let year: i64 = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+pub(crate) fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let year: i64 = year - i64::from(month <= 2);
+    let era: i64 = year.div_euclid(400);
+    let year_of_era: i64 = year.rem_euclid(400);
+    let month: i64 = month as i64;
+    let day_of_year: i64 =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
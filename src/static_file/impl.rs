@@ -0,0 +1,264 @@
+use crate::*;
+
+impl StaticFiles {
+    pub fn new<R>(root: R) -> Self
+    where
+        R: Into<PathBuf>,
+    {
+        Self {
+            root: root.into(),
+            index_file: Some(DEFAULT_INDEX_FILE.to_string()),
+            precompressed: false,
+        }
+    }
+
+    pub(crate) async fn serve(&self, ctx: &Context) {
+        let request_path: String = ctx
+            .get_route_param(STATIC_FILE_PARAM)
+            .await
+            .unwrap_or_default();
+        let Some(file_path) = self.resolve(&request_path).await else {
+            Self::send_status(ctx, 404, "Not Found").await;
+            return;
+        };
+        let content_type: &str = content_type_for(&file_path);
+        let (file_path, content_encoding): StaticFileVariant =
+            self.select_variant(ctx, file_path).await;
+        let Ok(metadata) = fs::metadata(&file_path).await else {
+            Self::send_status(ctx, 404, "Not Found").await;
+            return;
+        };
+        let len: u64 = metadata.len();
+        let modified: u64 = metadata
+            .modified()
+            .ok()
+            .and_then(|modified: SystemTime| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration: Duration| duration.as_secs())
+            .unwrap_or_default();
+        let etag: String = format!("\"{:x}-{:x}\"", len, modified);
+        ctx.set_response_header(ETAG, &etag)
+            .await
+            .set_response_header(LAST_MODIFIED, http_date(modified))
+            .await
+            .set_response_header(ACCEPT_RANGES, BYTES)
+            .await;
+        if *self.get_precompressed() {
+            ctx.set_response_header(VARY, ACCEPT_ENCODING).await;
+        }
+        if let Some(content_encoding) = content_encoding {
+            ctx.set_response_header(CONTENT_ENCODING, content_encoding)
+                .await;
+        }
+        if Self::is_not_modified(ctx, &etag, modified).await {
+            Self::send_status(ctx, 304, "").await;
+            return;
+        }
+        let range_request: RangeRequest = match ctx.get_request_header(RANGE).await {
+            Some(range) if Self::is_range_fresh(ctx, &etag, modified).await => {
+                parse_range(&range, len)
+            }
+            _ => RangeRequest::Full,
+        };
+        let (status_code, parts): (ResponseStatusCode, VecStaticFilePart) = match range_request {
+            RangeRequest::Full => {
+                ctx.set_response_header(CONTENT_TYPE, content_type).await;
+                (200, vec![StaticFilePart::File(0, len)])
+            }
+            RangeRequest::Unsatisfiable => {
+                ctx.set_response_header(CONTENT_RANGE, format!("{} */{}", BYTES, len))
+                    .await;
+                Self::send_status(ctx, 416, "").await;
+                return;
+            }
+            RangeRequest::Partial(ranges) if ranges.len() == 1 => {
+                let (start, end): ByteRange = ranges[0];
+                ctx.set_response_header(CONTENT_TYPE, content_type)
+                    .await
+                    .set_response_header(
+                        CONTENT_RANGE,
+                        format!("{} {}-{}/{}", BYTES, start, end, len),
+                    )
+                    .await;
+                (206, vec![StaticFilePart::File(start, end - start + 1)])
+            }
+            RangeRequest::Partial(ranges) => {
+                let boundary: String =
+                    format!("{}{:x}{:x}", MULTIPART_BOUNDARY_PREFIX, len, modified);
+                ctx.set_response_header(
+                    CONTENT_TYPE,
+                    format!("{}{}", MULTIPART_BYTERANGES, boundary),
+                )
+                .await;
+                let parts: VecStaticFilePart =
+                    Self::multipart_parts(&ranges, len, content_type, &boundary);
+                (206, parts)
+            }
+        };
+        let content_length: u64 = parts
+            .iter()
+            .map(|part: &StaticFilePart| match part {
+                StaticFilePart::Bytes(bytes) => bytes.len() as u64,
+                StaticFilePart::File(_, size) => *size,
+            })
+            .sum();
+        ctx.set_response_header(CONTENT_LENGTH, content_length.to_string())
+            .await;
+        if ctx.get_request_method().await.to_string() == HEAD {
+            let _ = ctx.send_response_head(status_code).await;
+            return;
+        }
+        let Ok(mut file) = fs::File::open(&file_path).await else {
+            Self::send_status(ctx, 500, "").await;
+            return;
+        };
+        if Self::send_parts(ctx, status_code, &mut file, &parts)
+            .await
+            .is_err()
+        {
+            let _ = ctx.close().await;
+        }
+    }
+
+    async fn resolve(&self, request_path: &str) -> OptionPathBuf {
+        let relative_path: PathBuf = sanitize_path(request_path)?;
+        let root: PathBuf = fs::canonicalize(self.get_root()).await.ok()?;
+        let mut path: PathBuf = fs::canonicalize(root.join(relative_path)).await.ok()?;
+        if fs::metadata(&path).await.ok()?.is_dir() {
+            let index_file: &String = self.get_index_file().as_ref()?;
+            path = fs::canonicalize(path.join(index_file)).await.ok()?;
+        }
+        if !path.starts_with(&root) || !fs::metadata(&path).await.ok()?.is_file() {
+            return None;
+        }
+        Some(path)
+    }
+
+    async fn select_variant(&self, ctx: &Context, file_path: PathBuf) -> StaticFileVariant {
+        if !*self.get_precompressed() {
+            return (file_path, None);
+        }
+        let accept_encoding: String = ctx
+            .get_request_header(ACCEPT_ENCODING)
+            .await
+            .unwrap_or_default();
+        for (encoding, extension) in PRECOMPRESSED_ENCODINGS {
+            if !accepts_encoding(&accept_encoding, encoding) {
+                continue;
+            }
+            let mut variant_path: OsString = file_path.clone().into_os_string();
+            variant_path.push(format!(".{}", extension));
+            let variant_path: PathBuf = PathBuf::from(variant_path);
+            if fs::metadata(&variant_path)
+                .await
+                .is_ok_and(|metadata: Metadata| metadata.is_file())
+            {
+                return (variant_path, Some(*encoding));
+            }
+        }
+        (file_path, None)
+    }
+
+    async fn is_not_modified(ctx: &Context, etag: &str, modified: u64) -> bool {
+        if let Some(if_none_match) = ctx.get_request_header(IF_NONE_MATCH).await {
+            return etag_matches(&if_none_match, etag);
+        }
+        ctx.get_request_header(IF_MODIFIED_SINCE)
+            .await
+            .and_then(|since: String| parse_http_date(&since))
+            .is_some_and(|since: u64| modified <= since)
+    }
+
+    async fn is_range_fresh(ctx: &Context, etag: &str, modified: u64) -> bool {
+        let Some(if_range) = ctx.get_request_header(IF_RANGE).await else {
+            return true;
+        };
+        let if_range: &str = if_range.trim();
+        if if_range.starts_with('"') {
+            return if_range == etag;
+        }
+        parse_http_date(if_range) == Some(modified)
+    }
+
+    fn multipart_parts(
+        ranges: &[ByteRange],
+        len: u64,
+        content_type: &str,
+        boundary: &str,
+    ) -> VecStaticFilePart {
+        let mut parts: VecStaticFilePart = vec![];
+        for (index, (start, end)) in ranges.iter().enumerate() {
+            let separator: &str = if index == 0 { "" } else { "\r\n" };
+            let part_head: String = format!(
+                "{}--{}\r\n{}: {}\r\n{}: {} {}-{}/{}\r\n\r\n",
+                separator,
+                boundary,
+                CONTENT_TYPE,
+                content_type,
+                CONTENT_RANGE,
+                BYTES,
+                start,
+                end,
+                len
+            );
+            parts.push(StaticFilePart::Bytes(part_head.into_bytes()));
+            parts.push(StaticFilePart::File(*start, end - start + 1));
+        }
+        parts.push(StaticFilePart::Bytes(
+            format!("\r\n--{}--\r\n", boundary).into_bytes(),
+        ));
+        parts
+    }
+
+    async fn send_parts(
+        ctx: &Context,
+        status_code: ResponseStatusCode,
+        file: &mut fs::File,
+        parts: &[StaticFilePart],
+    ) -> IoResult<()> {
+        let to_io_error = |err: ResponseError| IoError::other(err.to_string());
+        ctx.send_response_head(status_code)
+            .await
+            .map_err(to_io_error)?;
+        let mut buffer: Vec<u8> = vec![0; STATIC_FILE_CHUNK_SIZE];
+        for part in parts {
+            match part {
+                StaticFilePart::Bytes(bytes) => {
+                    ctx.send_response_body(bytes.clone())
+                        .await
+                        .map_err(to_io_error)?;
+                }
+                StaticFilePart::File(offset, size) => {
+                    file.seek(SeekFrom::Start(*offset)).await?;
+                    let mut remaining: u64 = *size;
+                    while remaining > 0 {
+                        let chunk_size: usize = remaining.min(buffer.len() as u64) as usize;
+                        file.read_exact(&mut buffer[..chunk_size]).await?;
+                        ctx.send_response_body(buffer[..chunk_size].to_vec())
+                            .await
+                            .map_err(to_io_error)?;
+                        remaining -= chunk_size as u64;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_status<T>(ctx: &Context, status_code: ResponseStatusCode, body: T)
+    where
+        T: Into<ResponseBody>,
+    {
+        let body: ResponseBody = body.into();
+        if status_code != 304 {
+            ctx.set_response_header(CONTENT_LENGTH, body.len().to_string())
+                .await;
+        }
+        if ctx.send_response_head(status_code).await.is_err()
+            || body.is_empty()
+            || ctx.get_request_method().await.to_string() == HEAD
+        {
+            return;
+        }
+        let _ = ctx.send_response_body(body).await;
+    }
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+#[derive(Clone, Debug, Getter, Setter)]
+pub struct StaticFiles {
+    #[get(pub)]
+    #[set(pub)]
+    pub(super) root: PathBuf,
+    #[get(pub)]
+    #[set(pub)]
+    pub(super) index_file: OptionString,
+    #[get(pub)]
+    #[set(pub)]
+    pub(super) precompressed: bool,
+}
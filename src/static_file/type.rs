@@ -0,0 +1,8 @@
+use crate::*;
+
+pub(crate) type ByteRange = (u64, u64);
+pub(crate) type VecByteRange = Vec<ByteRange>;
+pub(crate) type VecStaticFilePart = Vec<StaticFilePart>;
+pub(crate) type ArcStaticFiles = Arc<StaticFiles>;
+pub(crate) type OptionPathBuf = Option<PathBuf>;
+pub(crate) type StaticFileVariant = (PathBuf, Option<&'static str>);
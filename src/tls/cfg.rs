@@ -0,0 +1,301 @@
+use crate::*;
+
+#[cfg(test)]
+type TestTlsCert = (String, String, Vec<u8>);
+
+#[cfg(test)]
+fn generate_tls_cert(server_name: &str) -> TestTlsCert {
+    let rcgen::CertifiedKey { cert, key_pair } =
+        rcgen::generate_simple_self_signed(vec![server_name.to_string()]).unwrap();
+    (cert.pem(), key_pair.serialize_pem(), cert.der().to_vec())
+}
+
+#[cfg(test)]
+async fn connect_tls(
+    addr: SocketAddr,
+    root_pem: &str,
+    server_name: &str,
+    alpn_protocols: &[&str],
+    client_cert: Option<&TestTlsCert>,
+) -> IoResult<tokio_rustls::client::TlsStream<TcpStream>> {
+    use rustls::{ClientConfig, pki_types::ServerName};
+    use tokio_rustls::TlsConnector;
+
+    let mut root_store: RootCertStore = RootCertStore::empty();
+    for cert in CertificateDer::pem_slice_iter(root_pem.as_bytes()) {
+        root_store.add(cert.unwrap()).unwrap();
+    }
+    let builder = ClientConfig::builder_with_provider(tls_provider())
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(root_store);
+    let mut client_config: ClientConfig = match client_cert {
+        Some((cert_pem, key_pem, _)) => {
+            let certs: VecCertificateDer = CertificateDer::pem_slice_iter(cert_pem.as_bytes())
+                .collect::<Result<VecCertificateDer, _>>()
+                .unwrap();
+            let key: PrivateKeyDer<'static> =
+                PrivateKeyDer::from_pem_slice(key_pem.as_bytes()).unwrap();
+            builder.with_client_auth_cert(certs, key).unwrap()
+        }
+        None => builder.with_no_client_auth(),
+    };
+    client_config.alpn_protocols = alpn_protocols
+        .iter()
+        .map(|protocol: &&str| protocol.as_bytes().to_vec())
+        .collect();
+    let server_name: ServerName<'static> = ServerName::try_from(server_name.to_string()).unwrap();
+    let stream: TcpStream = TcpStream::connect(addr).await?;
+    TlsConnector::from(Arc::new(client_config))
+        .connect(server_name, stream)
+        .await
+}
+
+#[cfg(test)]
+async fn tls_request(
+    addr: SocketAddr,
+    root_pem: &str,
+    server_name: &str,
+    path: &str,
+    client_cert: Option<&TestTlsCert>,
+) -> IoResult<Vec<u8>> {
+    let alpn_protocols: &[&str] = &[TLS_ALPN_HTTP1_1];
+    let mut stream: tokio_rustls::client::TlsStream<TcpStream> =
+        connect_tls(addr, root_pem, server_name, alpn_protocols, client_cert).await?;
+    let request: String = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path, server_name
+    );
+    stream.write_all(request.as_bytes()).await?;
+    let mut response: Vec<u8> = vec![];
+    stream.read_to_end(&mut response).await?;
+    Ok(response)
+}
+
+#[cfg(test)]
+async fn start_tls_server(server: &Server) -> ServerHandle {
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server
+        .get("/alpn", |ctx: Context| async move {
+            let protocol: String = ctx.get_tls_alpn_protocol().await.unwrap_or_default();
+            let _ = ctx.send_response(200, protocol).await;
+        })
+        .await;
+    server
+        .get("/peer", |ctx: Context| async move {
+            let peer_certificate: Vec<u8> =
+                ctx.get_tls_peer_certificate().await.unwrap_or_default();
+            let _ = ctx.send_response(200, peer_certificate).await;
+        })
+        .await;
+    server.start().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_tls_sni_and_alpn() {
+    let default_cert: TestTlsCert = generate_tls_cert("localhost");
+    let a_cert: TestTlsCert = generate_tls_cert("a.localhost");
+    let b_cert: TestTlsCert = generate_tls_cert("b.localhost");
+    let wildcard_cert: TestTlsCert = generate_tls_cert("*.wild.localhost");
+    let server: Server = Server::new();
+    server
+        .tls_pem(default_cert.0.clone(), default_cert.1.clone())
+        .await;
+    server
+        .tls_sni_pem("A.localhost", a_cert.0.clone(), a_cert.1.clone())
+        .await;
+    server
+        .tls_sni_pem("b.localhost", b_cert.0.clone(), b_cert.1.clone())
+        .await;
+    server
+        .tls_sni_pem(
+            "*.wild.localhost",
+            wildcard_cert.0.clone(),
+            wildcard_cert.1.clone(),
+        )
+        .await;
+    let handle: ServerHandle = start_tls_server(&server).await;
+    let addr: SocketAddr = handle.local_addr();
+    for (root_pem, server_name) in [
+        (&default_cert.0, "localhost"),
+        (&a_cert.0, "a.localhost"),
+        (&b_cert.0, "b.localhost"),
+        (&wildcard_cert.0, "x.wild.localhost"),
+    ] {
+        let response: Vec<u8> = tls_request(addr, root_pem, server_name, "/alpn", None)
+            .await
+            .unwrap();
+        let response: String = String::from_utf8_lossy(&response).to_string();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with(TLS_ALPN_HTTP1_1));
+    }
+    assert!(
+        tls_request(addr, &a_cert.0, "b.localhost", "/alpn", None)
+            .await
+            .is_err()
+    );
+    let mut plain: TcpStream = TcpStream::connect(addr).await.unwrap();
+    plain
+        .write_all(b"GET /alpn HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response: Vec<u8> = vec![];
+    let _ = plain.read_to_end(&mut response).await;
+    assert!(!response.starts_with(b"HTTP/1.1 200"));
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_tls_reload_from_files() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("hyperlane-tls-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cert_path: PathBuf = dir.join("cert.pem");
+    let key_path: PathBuf = dir.join("key.pem");
+    let old_cert: TestTlsCert = generate_tls_cert("localhost");
+    std::fs::write(&cert_path, &old_cert.0).unwrap();
+    std::fs::write(&key_path, &old_cert.1).unwrap();
+    let server: Server = Server::new();
+    server.tls_pem_file(&cert_path, &key_path).await;
+    let handle: ServerHandle = start_tls_server(&server).await;
+    let addr: SocketAddr = handle.local_addr();
+    assert!(
+        tls_request(addr, &old_cert.0, "localhost", "/alpn", None)
+            .await
+            .is_ok()
+    );
+    let new_cert: TestTlsCert = generate_tls_cert("localhost");
+    std::fs::write(&cert_path, &new_cert.0).unwrap();
+    std::fs::write(&key_path, &new_cert.1).unwrap();
+    assert!(
+        tls_request(addr, &old_cert.0, "localhost", "/alpn", None)
+            .await
+            .is_ok()
+    );
+    server.reload_tls().await.unwrap();
+    assert!(
+        tls_request(addr, &new_cert.0, "localhost", "/alpn", None)
+            .await
+            .is_ok()
+    );
+    assert!(
+        tls_request(addr, &old_cert.0, "localhost", "/alpn", None)
+            .await
+            .is_err()
+    );
+    std::fs::write(&key_path, b"not a key").unwrap();
+    assert!(matches!(
+        server.reload_tls().await,
+        Err(ServerError::TlsError(_))
+    ));
+    assert!(
+        tls_request(addr, &new_cert.0, "localhost", "/alpn", None)
+            .await
+            .is_ok()
+    );
+    handle.shutdown().await;
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_tls_invalid_config() {
+    let server: Server = Server::new();
+    server.host("127.0.0.1").await;
+    server.port(0).await;
+    server.tls_pem("not a cert", "not a key").await;
+    assert!(matches!(
+        server.start().await,
+        Err(ServerError::TlsError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_tls_client_certificate() {
+    let server_cert: TestTlsCert = generate_tls_cert("localhost");
+    let client_cert: TestTlsCert = generate_tls_cert("client.localhost");
+    let server: Server = Server::new();
+    server
+        .tls_pem(server_cert.0.clone(), server_cert.1.clone())
+        .await;
+    server.tls_client_ca_pem(client_cert.0.clone()).await;
+    let handle: ServerHandle = start_tls_server(&server).await;
+    let addr: SocketAddr = handle.local_addr();
+    let response: Vec<u8> = tls_request(
+        addr,
+        &server_cert.0,
+        "localhost",
+        "/peer",
+        Some(&client_cert),
+    )
+    .await
+    .unwrap();
+    assert!(response.starts_with(b"HTTP/1.1 200"));
+    assert!(response.ends_with(&client_cert.2));
+    let response: Vec<u8> = tls_request(addr, &server_cert.0, "localhost", "/peer", None)
+        .await
+        .unwrap();
+    assert!(response.starts_with(b"HTTP/1.1 200"));
+    assert!(!response.ends_with(&client_cert.2));
+    handle.shutdown().await;
+    let required: Server = Server::new();
+    required
+        .tls_pem(server_cert.0.clone(), server_cert.1.clone())
+        .await;
+    required.tls_client_ca_pem(client_cert.0.clone()).await;
+    required.tls_client_auth_required(true).await;
+    let handle: ServerHandle = start_tls_server(&required).await;
+    let addr: SocketAddr = handle.local_addr();
+    let response: IoResult<Vec<u8>> =
+        tls_request(addr, &server_cert.0, "localhost", "/peer", None).await;
+    assert!(response.map_or(true, |response: Vec<u8>| response.is_empty()));
+    assert!(
+        tls_request(
+            addr,
+            &server_cert.0,
+            "localhost",
+            "/peer",
+            Some(&client_cert)
+        )
+        .await
+        .is_ok()
+    );
+    handle.shutdown().await;
+}
+
+#[cfg(feature = "http2")]
+#[tokio::test]
+async fn test_tls_alpn_h2() {
+    let cert: TestTlsCert = generate_tls_cert("localhost");
+    let server: Server = Server::new();
+    server.tls_pem(cert.0.clone(), cert.1.clone()).await;
+    let handle: ServerHandle = start_tls_server(&server).await;
+    let alpn_protocols: &[&str] = &[TLS_ALPN_H2, TLS_ALPN_HTTP1_1];
+    let stream: tokio_rustls::client::TlsStream<TcpStream> = connect_tls(
+        handle.local_addr(),
+        &cert.0,
+        "localhost",
+        alpn_protocols,
+        None,
+    )
+    .await
+    .unwrap();
+    let (mut client, connection) = h2::client::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request: Http2Request<()> = Http2Request::builder()
+        .method("GET")
+        .uri("https://localhost/alpn")
+        .body(())
+        .unwrap();
+    let (response, _) = client.send_request(request, true).unwrap();
+    let response: Http2Response<RecvStream> = response.await.unwrap();
+    assert_eq!(response.status(), 200);
+    let mut body: RecvStream = response.into_body();
+    let mut data: Vec<u8> = vec![];
+    while let Some(chunk) = body.data().await {
+        data.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(data, TLS_ALPN_H2.as_bytes());
+    handle
+        .shutdown_with_timeout(std::time::Duration::from_secs(1))
+        .await;
+}
@@ -0,0 +1,9 @@
+#[cfg(feature = "http2")]
+pub(crate) const TLS_ALPN_H2: &str = "h2";
+pub(crate) const TLS_ALPN_HTTP1_1: &str = "http/1.1";
+#[cfg(feature = "http2")]
+pub(crate) const TLS_ALPN_PROTOCOLS: &[&str] = &[TLS_ALPN_H2, TLS_ALPN_HTTP1_1];
+#[cfg(not(feature = "http2"))]
+pub(crate) const TLS_ALPN_PROTOCOLS: &[&str] = &[TLS_ALPN_HTTP1_1];
+pub(crate) const TLS_WILDCARD_PREFIX: &str = "*.";
+pub(crate) const TLS_HANDSHAKE_TIMED_OUT: &str = "TLS handshake timed out";
@@ -0,0 +1,7 @@
+use crate::*;
+
+#[derive(Clone)]
+pub(crate) enum TlsPemSource {
+    Memory(Vec<u8>),
+    File(PathBuf),
+}
@@ -0,0 +1,38 @@
+use crate::*;
+
+pub(crate) fn tls_error<E: Display>(error: E) -> ServerError {
+    ServerError::TlsError(error.to_string())
+}
+
+pub(crate) fn load_certified_key(cert_pem: &[u8], key_pem: &[u8]) -> ResultArcCertifiedKey {
+    let certs: VecCertificateDer = CertificateDer::pem_slice_iter(cert_pem)
+        .collect::<Result<VecCertificateDer, _>>()
+        .map_err(tls_error)?;
+    if certs.is_empty() {
+        return Err(ServerError::TlsError(
+            "no certificate found in PEM data".to_string(),
+        ));
+    }
+    let key: PrivateKeyDer<'static> = PrivateKeyDer::from_pem_slice(key_pem).map_err(tls_error)?;
+    let signing_key = any_supported_type(&key).map_err(tls_error)?;
+    Ok(Arc::new(CertifiedKey::new(certs, signing_key)))
+}
+
+pub(crate) fn load_root_store(ca_pem: &[u8]) -> ResultRootCertStore {
+    let mut root_store: RootCertStore = RootCertStore::empty();
+    for cert in CertificateDer::pem_slice_iter(ca_pem) {
+        root_store
+            .add(cert.map_err(tls_error)?)
+            .map_err(tls_error)?;
+    }
+    if root_store.is_empty() {
+        return Err(ServerError::TlsError(
+            "no CA certificate found in PEM data".to_string(),
+        ));
+    }
+    Ok(root_store)
+}
+
+pub(crate) fn tls_provider() -> ArcCryptoProvider {
+    Arc::new(default_provider())
+}
@@ -0,0 +1,156 @@
+use crate::*;
+
+impl TlsPemSource {
+    pub(crate) async fn read(&self) -> ResultVecU8 {
+        match self {
+            Self::Memory(data) => Ok(data.clone()),
+            Self::File(path) => fs::read(path).await.map_err(|error: IoError| {
+                ServerError::TlsError(format!("{}{}{}", path.display(), COLON_SPACE, error))
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for TlsSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let certified_keys: TlsCertifiedKeys = self.get_certified_keys();
+        f.debug_struct("TlsSettings")
+            .field("default_cert", &certified_keys.default_key.is_some())
+            .field(
+                "server_names",
+                &certified_keys.server_name_keys.keys().collect::<Vec<_>>(),
+            )
+            .field(
+                "client_auth_required",
+                &self.client_auth_required.load(Ordering::Relaxed),
+            )
+            .finish()
+    }
+}
+
+impl ResolvesServerCert for TlsSettings {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> OptionArcCertifiedKey {
+        let certified_keys = self
+            .certified_keys
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let server_name: Option<String> = client_hello.server_name().map(str::to_ascii_lowercase);
+        if let Some(server_name) = server_name {
+            if let Some(key) = certified_keys.server_name_keys.get(&server_name) {
+                return Some(key.clone());
+            }
+            if let Some((_, parent)) = server_name.split_once('.') {
+                let wildcard: String = format!("{}{}", TLS_WILDCARD_PREFIX, parent);
+                if let Some(key) = certified_keys.server_name_keys.get(&wildcard) {
+                    return Some(key.clone());
+                }
+            }
+        }
+        certified_keys.default_key.clone()
+    }
+}
+
+impl TlsSettings {
+    pub(crate) fn add_cert(
+        &self,
+        server_name: OptionString,
+        cert: TlsPemSource,
+        key: TlsPemSource,
+    ) {
+        let server_name: OptionString =
+            server_name.map(|server_name: String| server_name.to_ascii_lowercase());
+        let mut cert_sources = self
+            .cert_sources
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        cert_sources.retain(|(name, _, _): &TlsCertSource| *name != server_name);
+        cert_sources.push((server_name, cert, key));
+    }
+
+    pub(crate) fn set_client_ca(&self, ca: TlsPemSource) {
+        *self
+            .client_ca
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(ca);
+    }
+
+    pub(crate) fn set_client_auth_required(&self, required: bool) {
+        self.client_auth_required.store(required, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self
+            .cert_sources
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    fn get_certified_keys(&self) -> TlsCertifiedKeys {
+        self.certified_keys
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    async fn load_certified_keys(&self) -> ResultTlsCertifiedKeys {
+        let cert_sources: VecTlsCertSource = self
+            .cert_sources
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let mut certified_keys: TlsCertifiedKeys = TlsCertifiedKeys::default();
+        for (server_name, cert, key) in cert_sources {
+            let key: ArcCertifiedKey = load_certified_key(&cert.read().await?, &key.read().await?)?;
+            match server_name {
+                Some(server_name) => {
+                    certified_keys.server_name_keys.insert(server_name, key);
+                }
+                None => certified_keys.default_key = Some(key),
+            }
+        }
+        Ok(certified_keys)
+    }
+
+    pub(crate) async fn reload(&self) -> ServerResult {
+        let certified_keys: TlsCertifiedKeys = self.load_certified_keys().await?;
+        *self
+            .certified_keys
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = certified_keys;
+        Ok(())
+    }
+
+    pub(crate) async fn build_acceptor(self: &Arc<Self>) -> ResultTlsAcceptor {
+        self.reload().await?;
+        let client_ca: OptionTlsPemSource = self
+            .client_ca
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let provider: ArcCryptoProvider = tls_provider();
+        let builder = RustlsServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_error)?;
+        let builder = match client_ca {
+            Some(client_ca) => {
+                let root_store: RootCertStore = load_root_store(&client_ca.read().await?)?;
+                let verifier_builder =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(root_store), provider);
+                let verifier_builder = if self.client_auth_required.load(Ordering::Relaxed) {
+                    verifier_builder
+                } else {
+                    verifier_builder.allow_unauthenticated()
+                };
+                builder.with_client_cert_verifier(verifier_builder.build().map_err(tls_error)?)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let mut tls_config: RustlsServerConfig = builder.with_cert_resolver(self.clone());
+        tls_config.alpn_protocols = TLS_ALPN_PROTOCOLS
+            .iter()
+            .map(|protocol: &&str| protocol.as_bytes().to_vec())
+            .collect();
+        Ok(TlsAcceptor::from(Arc::new(tls_config)))
+    }
+}
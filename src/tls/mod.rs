@@ -0,0 +1,13 @@
+pub(crate) mod cfg;
+pub(crate) mod r#const;
+pub(crate) mod r#enum;
+pub(crate) mod r#fn;
+pub(crate) mod r#impl;
+pub(crate) mod r#struct;
+pub(crate) mod r#type;
+
+pub(crate) use r#const::*;
+pub(crate) use r#enum::*;
+pub(crate) use r#fn::*;
+pub(crate) use r#struct::*;
+pub(crate) use r#type::*;
@@ -0,0 +1,15 @@
+use crate::*;
+
+#[derive(Default)]
+pub(crate) struct TlsSettings {
+    pub(super) cert_sources: StdRwLock<VecTlsCertSource>,
+    pub(super) certified_keys: StdRwLock<TlsCertifiedKeys>,
+    pub(super) client_ca: StdRwLock<OptionTlsPemSource>,
+    pub(super) client_auth_required: AtomicBool,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct TlsCertifiedKeys {
+    pub(super) default_key: OptionArcCertifiedKey,
+    pub(super) server_name_keys: HashMapXxHash3_64<String, ArcCertifiedKey>,
+}
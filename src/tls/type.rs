@@ -0,0 +1,17 @@
+use crate::*;
+
+pub(crate) type ArcTlsSettings = Arc<TlsSettings>;
+pub(crate) type TlsCertSource = (OptionString, TlsPemSource, TlsPemSource);
+pub(crate) type VecTlsCertSource = Vec<TlsCertSource>;
+pub(crate) type OptionTlsPemSource = Option<TlsPemSource>;
+pub(crate) type ArcCertifiedKey = Arc<CertifiedKey>;
+pub(crate) type OptionArcCertifiedKey = Option<ArcCertifiedKey>;
+pub(crate) type ArcCryptoProvider = Arc<CryptoProvider>;
+pub(crate) type VecCertificateDer = Vec<CertificateDer<'static>>;
+pub(crate) type ResultVecU8 = Result<Vec<u8>, ServerError>;
+pub(crate) type ResultArcCertifiedKey = Result<ArcCertifiedKey, ServerError>;
+pub(crate) type ResultRootCertStore = Result<RootCertStore, ServerError>;
+pub(crate) type ResultTlsCertifiedKeys = Result<TlsCertifiedKeys, ServerError>;
+pub(crate) type ResultTlsAcceptor = Result<TlsAcceptor, ServerError>;
+pub(crate) type OptionTlsAcceptor = Option<TlsAcceptor>;
+pub(crate) type VecVecU8 = Vec<Vec<u8>>;